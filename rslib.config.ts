@@ -3,7 +3,7 @@ import { defineConfig } from '@rslib/core'
 export default defineConfig({
   source: {
     entry: {
-      index: ['./src/**', '!src/lib.rs'],
+      index: ['./src/**', '!src/**/*.rs'],
     },
   },
   lib: [
@@ -0,0 +1,366 @@
+use crate::{
+  get_predicate_function, ArmKind, MatchOptions, MatchedArm, PatternTable, GROUP_ORDER, PREFIX_WHEN,
+};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+  type AbortSignal;
+
+  #[wasm_bindgen(method, getter)]
+  fn aborted(this: &AbortSignal) -> bool;
+
+  #[wasm_bindgen(method, getter)]
+  fn reason(this: &AbortSignal) -> JsValue;
+
+  #[wasm_bindgen(method, js_name = addEventListener)]
+  fn add_event_listener(this: &AbortSignal, kind: &str, listener: &Function);
+
+  #[wasm_bindgen(method, js_name = removeEventListener)]
+  fn remove_event_listener(this: &AbortSignal, kind: &str, listener: &Function);
+
+  #[wasm_bindgen(js_name = setTimeout)]
+  fn set_timeout(handler: &Function, timeout: f64) -> JsValue;
+
+  #[wasm_bindgen(js_name = clearTimeout)]
+  fn clear_timeout(id: &JsValue);
+}
+
+struct AsyncOptions {
+  signal: Option<AbortSignal>,
+  timeout: Option<f64>,
+  concurrent: bool,
+}
+
+impl AsyncOptions {
+  fn from_js(options: Option<&Object>) -> Result<Self, JsValue> {
+    let Some(opts) = options else {
+      return Ok(Self {
+        signal: None,
+        timeout: None,
+        concurrent: false,
+      });
+    };
+
+    let signal = Reflect::get(opts, &JsValue::from_str("signal"))?;
+    let signal = if signal.is_undefined() || signal.is_null() {
+      None
+    } else {
+      Some(signal.unchecked_into::<AbortSignal>())
+    };
+
+    let timeout = Reflect::get(opts, &JsValue::from_str("timeout"))?;
+    let timeout = if timeout.is_undefined() {
+      None
+    } else {
+      match timeout.as_f64() {
+        Some(ms) if ms >= 0.0 => Some(ms),
+        _ => {
+          return Err(JsValue::from_str(
+            "timeout must be a non-negative number of milliseconds",
+          ))
+        }
+      }
+    };
+
+    let predicates = Reflect::get(opts, &JsValue::from_str("predicates"))?;
+    let concurrent = match predicates.as_string().as_deref() {
+      None if predicates.is_undefined() => false,
+      Some("sequential") => false,
+      Some("concurrent") => true,
+      _ => {
+        return Err(JsValue::from_str(
+          "predicates must be either 'sequential' or 'concurrent'",
+        ))
+      }
+    };
+
+    Ok(Self {
+      signal,
+      timeout,
+      concurrent,
+    })
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Interruption {
+  Aborted,
+  TimedOut,
+}
+
+impl Interruption {
+  fn as_str(self) -> &'static str {
+    match self {
+      Interruption::Aborted => "abort",
+      Interruption::TimedOut => "timeout",
+    }
+  }
+}
+
+enum Outcome {
+  Resolved(JsValue),
+  Rejected(JsValue),
+}
+
+struct Timer {
+  promise: Promise,
+  id: JsValue,
+}
+
+impl Timer {
+  fn start(timeout: f64, sentinel: &JsValue) -> Self {
+    let mut id = JsValue::UNDEFINED;
+    let promise = Promise::new(&mut |resolve, _reject| {
+      id = set_timeout(&resolve.bind1(&JsValue::NULL, sentinel), timeout);
+    });
+    Self { promise, id }
+  }
+}
+
+impl Drop for Timer {
+  fn drop(&mut self) {
+    clear_timeout(&self.id);
+  }
+}
+
+struct Interrupts {
+  signal: Option<AbortSignal>,
+  listener: Option<Function>,
+  aborted: Option<Promise>,
+  aborted_sentinel: JsValue,
+  timeout: Option<f64>,
+  timed_out_sentinel: JsValue,
+}
+
+impl Interrupts {
+  fn new(options: AsyncOptions) -> Self {
+    let aborted_sentinel: JsValue = Object::new().into();
+    let mut listener = None;
+    let aborted = options.signal.as_ref().map(|signal| {
+      if signal.aborted() {
+        Promise::resolve(&aborted_sentinel)
+      } else {
+        Promise::new(&mut |resolve, _reject| {
+          let on_abort = resolve.bind1(&JsValue::NULL, &aborted_sentinel);
+          signal.add_event_listener("abort", &on_abort);
+          listener = Some(on_abort);
+        })
+      }
+    });
+
+    Self {
+      signal: options.signal,
+      listener,
+      aborted,
+      aborted_sentinel,
+      timeout: options.timeout,
+      timed_out_sentinel: Object::new().into(),
+    }
+  }
+
+  fn start_timer(&self) -> Option<Timer> {
+    self
+      .timeout
+      .map(|timeout| Timer::start(timeout, &self.timed_out_sentinel))
+  }
+
+  fn error(&self, interruption: Interruption, key: Option<&str>) -> JsValue {
+    let message = match (interruption, key) {
+      (Interruption::Aborted, None) => "Matching was aborted before it started".to_string(),
+      (Interruption::Aborted, Some(key)) => {
+        format!("Matching was aborted while `{}` was pending", key)
+      }
+      (Interruption::TimedOut, key) => format!(
+        "Predicate `{}` did not settle within {}ms",
+        key.unwrap_or_default(),
+        self.timeout.unwrap_or_default()
+      ),
+    };
+    let error = js_sys::Error::new(&message);
+    error.set_name("MatchAbortError");
+    let _ = Reflect::set(
+      &error,
+      &JsValue::from_str("reason"),
+      &JsValue::from_str(interruption.as_str()),
+    );
+    if let Some(key) = key {
+      let _ = Reflect::set(&error, &JsValue::from_str("key"), &JsValue::from_str(key));
+    }
+    if let (Interruption::Aborted, Some(signal)) = (interruption, &self.signal) {
+      let _ = Reflect::set(&error, &JsValue::from_str("cause"), &signal.reason());
+    }
+    error.into()
+  }
+
+  fn check_aborted(&self, key: Option<&str>) -> Result<(), JsValue> {
+    match &self.signal {
+      Some(signal) if signal.aborted() => Err(self.error(Interruption::Aborted, key)),
+      _ => Ok(()),
+    }
+  }
+
+  async fn race(
+    &self,
+    pending: &Promise,
+    key: &str,
+    timer: Option<&Timer>,
+  ) -> Result<Outcome, JsValue> {
+    let contenders = Array::new();
+    if let Some(aborted) = &self.aborted {
+      contenders.push(aborted);
+    }
+    if let Some(timer) = timer {
+      contenders.push(&timer.promise);
+    }
+    contenders.push(pending);
+
+    match JsFuture::from(Promise::race(&contenders)).await {
+      Ok(settled) if Object::is(&settled, &self.aborted_sentinel) => {
+        Err(self.error(Interruption::Aborted, Some(key)))
+      }
+      Ok(settled) if Object::is(&settled, &self.timed_out_sentinel) => {
+        Err(self.error(Interruption::TimedOut, Some(key)))
+      }
+      Ok(settled) => Ok(Outcome::Resolved(settled)),
+      Err(err) => Ok(Outcome::Rejected(err)),
+    }
+  }
+}
+
+impl Drop for Interrupts {
+  fn drop(&mut self) {
+    if let (Some(signal), Some(listener)) = (&self.signal, &self.listener) {
+      signal.remove_event_listener("abort", listener);
+    }
+  }
+}
+
+struct PendingPredicate {
+  promise: Promise,
+  timer: Option<Timer>,
+}
+
+fn start_predicate(
+  pattern: &str,
+  value: &JsValue,
+  context: &JsValue,
+  interrupts: &Interrupts,
+) -> Option<PendingPredicate> {
+  let function_hash = &pattern[PREFIX_WHEN.len()..];
+  let promise = if function_hash == "true" {
+    Promise::resolve(&JsValue::TRUE)
+  } else {
+    let Ok(Some(predicate)) = get_predicate_function(function_hash) else {
+      return None;
+    };
+    match predicate.call2(&JsValue::NULL, value, context) {
+      Ok(result) => Promise::resolve(&result),
+      Err(err) => Promise::reject(&err),
+    }
+  };
+
+  Some(PendingPredicate {
+    promise,
+    timer: interrupts.start_timer(),
+  })
+}
+
+async fn first_when_arm(
+  table: &PatternTable<'_>,
+  value: &JsValue,
+  interrupts: &Interrupts,
+  concurrent: bool,
+) -> Result<Option<MatchedArm>, JsValue> {
+  let arms: Vec<&(String, JsValue)> = table
+    .groups()
+    .group(ArmKind::When)
+    .iter()
+    .filter(|(_, handler)| table.options.accepts(handler))
+    .collect();
+
+  let mut started: Vec<Option<PendingPredicate>> = if concurrent {
+    arms
+      .iter()
+      .map(|(pattern, _)| start_predicate(pattern, value, &table.options.context, interrupts))
+      .collect()
+  } else {
+    Vec::new()
+  };
+
+  for (i, (pattern, handler)) in arms.into_iter().enumerate() {
+    let predicate = if concurrent {
+      started[i].take()
+    } else {
+      start_predicate(pattern, value, &table.options.context, interrupts)
+    };
+    let Some(predicate) = predicate else {
+      continue;
+    };
+
+    let outcome = interrupts
+      .race(&predicate.promise, pattern, predicate.timer.as_ref())
+      .await?;
+    if matches!(outcome, Outcome::Resolved(result) if result.as_bool() == Some(true)) {
+      return Ok(Some(MatchedArm {
+        kind: ArmKind::When,
+        key: pattern.clone(),
+        handler: handler.clone(),
+      }));
+    }
+  }
+
+  Ok(None)
+}
+
+async fn find_async(
+  table: &PatternTable<'_>,
+  value: &JsValue,
+  interrupts: &Interrupts,
+  concurrent: bool,
+) -> Result<Option<MatchedArm>, JsValue> {
+  if let Some(arm) = PatternTable::first(|visit| table.visit_head(value, visit)) {
+    return Ok(Some(arm));
+  }
+
+  for kind in GROUP_ORDER {
+    let arm = if kind == ArmKind::When {
+      first_when_arm(table, value, interrupts, concurrent).await?
+    } else {
+      PatternTable::first(|visit| table.visit_group(kind, value, true, visit))
+    };
+    if arm.is_some() {
+      return Ok(arm);
+    }
+  }
+
+  Ok(table.default_arm())
+}
+
+#[wasm_bindgen(js_name = "matchAsync")]
+pub async fn match_async(
+  value: JsValue,
+  patterns: Object,
+  options: Option<Object>,
+) -> Result<JsValue, JsValue> {
+  let async_options = AsyncOptions::from_js(options.as_ref())?;
+  let concurrent = async_options.concurrent;
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(&patterns, &options);
+  table.check_collisions()?;
+  let interrupts = Interrupts::new(async_options);
+  interrupts.check_aborted(None)?;
+
+  let Some(arm) = find_async(&table, &value, &interrupts, concurrent).await? else {
+    return Err(table.no_match_error(&value));
+  };
+  interrupts.check_aborted(Some(&arm.key))?;
+
+  let result = Promise::resolve(&arm.call(&value, &options)?);
+  match interrupts.race(&result, &arm.key, None).await? {
+    Outcome::Resolved(result) => Ok(result),
+    Outcome::Rejected(err) => Err(err),
+  }
+}
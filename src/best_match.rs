@@ -0,0 +1,116 @@
+use crate::fuzzy::parse_fuzzy_key;
+use crate::{ArmKind, MatchOptions, MatchedArm, PatternTable};
+use js_sys::{Function, Object, Reflect};
+use std::ops::ControlFlow;
+use wasm_bindgen::prelude::*;
+
+const NEUTRAL_SCORE: f64 = 0.5;
+// `some()` and `type()` accept almost anything, so they rank with a bare `*`.
+const BROAD_SCORE: f64 = 0.1;
+
+fn default_score(arm: &MatchedArm, distance: Option<usize>) -> f64 {
+  match arm.kind {
+    ArmKind::Exact | ArmKind::Literal => 1.0,
+    ArmKind::Fuzzy => {
+      let max_distance = parse_fuzzy_key(&arm.key).map_or(0, |(max, _)| max);
+      let distance = distance.unwrap_or(max_distance) as f64;
+      NEUTRAL_SCORE + NEUTRAL_SCORE * (1.0 - distance / (max_distance as f64 + 1.0))
+    }
+    ArmKind::Wildcard => {
+      let total = arm.key.chars().count();
+      let literal = arm.key.chars().filter(|&c| c != '*' && c != '?').count();
+      BROAD_SCORE + (NEUTRAL_SCORE - BROAD_SCORE) * literal as f64 / total.max(1) as f64
+    }
+    ArmKind::Some | ArmKind::Type => BROAD_SCORE,
+    ArmKind::Default => 0.0,
+    _ => NEUTRAL_SCORE,
+  }
+}
+
+fn candidate_to_js(arm: &MatchedArm, score: f64, distance: Option<usize>) -> Object {
+  let candidate = arm.to_js();
+  let _ = Reflect::set(
+    &candidate,
+    &JsValue::from_str("score"),
+    &JsValue::from_f64(score),
+  );
+  if let Some(distance) = distance {
+    let _ = Reflect::set(
+      &candidate,
+      &JsValue::from_str("distance"),
+      &JsValue::from(distance as u32),
+    );
+  }
+  candidate
+}
+
+fn scorer(options: Option<&Object>) -> Result<Option<Function>, JsValue> {
+  let Some(opts) = options else {
+    return Ok(None);
+  };
+  let score = Reflect::get(opts, &JsValue::from_str("score"))?;
+  if score.is_undefined() {
+    return Ok(None);
+  }
+  score
+    .dyn_into::<Function>()
+    .map(Some)
+    .map_err(|_| JsValue::from_str("score must be a function"))
+}
+
+struct Scored {
+  arm: MatchedArm,
+  score: f64,
+}
+
+#[wasm_bindgen(js_name = "bestMatch")]
+pub fn best_match(
+  value: &JsValue,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<Object, JsValue> {
+  let scorer = scorer(options.as_ref())?;
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(patterns, &options);
+  table.check_collisions()?;
+
+  let mut candidates: Vec<MatchedArm> = Vec::new();
+  let _ = table.visit(value, true, &mut |arm| {
+    if !candidates.iter().any(|seen| seen.key == arm.key) {
+      candidates.push(arm);
+    }
+    ControlFlow::Continue(())
+  });
+  if candidates.is_empty() {
+    candidates.extend(table.default_arm());
+  }
+
+  // Candidates arrive in priority order, so ties keep the earlier arm.
+  let mut best: Option<Scored> = None;
+  for arm in candidates {
+    let distance = arm.distance(value, &options);
+    let mut score = default_score(&arm, distance);
+    if let Some(scorer) = &scorer {
+      let candidate = candidate_to_js(&arm, score, distance);
+      score = scorer
+        .call2(&JsValue::NULL, value, &candidate)?
+        .as_f64()
+        .filter(|score| score.is_finite())
+        .ok_or_else(|| JsValue::from_str("score must return a finite number"))?;
+    }
+    if best.as_ref().is_none_or(|best| score > best.score) {
+      best = Some(Scored { arm, score });
+    }
+  }
+
+  let Some(Scored { arm, score }) = best else {
+    return Err(table.no_match_error(value));
+  };
+  let result = candidate_to_js(&arm, score, arm.distance(value, &options));
+  Reflect::set(
+    &result,
+    &JsValue::from_str("result"),
+    &arm.call(value, &options)?,
+  )?;
+  Ok(result)
+}
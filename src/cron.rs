@@ -0,0 +1,220 @@
+use crate::date::{date_millis, parse_time_zone, DAY_MS, HOUR_MS, MAX_OFFSET_MINUTES};
+use crate::PREFIX_CRON;
+use wasm_bindgen::prelude::*;
+
+const MONTH_NAMES: [&str; 12] = [
+  "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+struct Field {
+  name: &'static str,
+  min: u32,
+  max: u32,
+  names: &'static [&'static str],
+}
+
+const FIELDS: [Field; 5] = [
+  Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+  },
+  Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+  },
+  Field {
+    name: "day-of-month",
+    min: 1,
+    max: 31,
+    names: &[],
+  },
+  Field {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &MONTH_NAMES,
+  },
+  Field {
+    name: "day-of-week",
+    min: 0,
+    max: 7,
+    names: &DAY_NAMES,
+  },
+];
+
+impl Field {
+  fn value(&self, text: &str) -> Result<u32, String> {
+    let value = match self
+      .names
+      .iter()
+      .position(|name| name.eq_ignore_ascii_case(text))
+    {
+      Some(index) => index as u32 + self.min,
+      None => text
+        .parse::<u32>()
+        .map_err(|_| format!("`{}` is not a valid {}", text, self.name))?,
+    };
+    if (self.min..=self.max).contains(&value) {
+      Ok(value)
+    } else {
+      Err(format!(
+        "{} {} is outside {}-{}",
+        self.name, value, self.min, self.max
+      ))
+    }
+  }
+
+  // `SUN` is 0, but as a range end it means 7, so `MON-SUN` is not descending.
+  fn range_end(&self, text: &str) -> Result<u32, String> {
+    let value = self.value(text)?;
+    let wraps = self.max as usize == self.min as usize + self.names.len();
+    if wraps && value == self.min && text.parse::<u32>().is_err() {
+      Ok(self.max)
+    } else {
+      Ok(value)
+    }
+  }
+
+  fn parse(&self, text: &str) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in text.split(',') {
+      let (range, step) = match part.split_once('/') {
+        Some((range, step)) => match step.parse::<u32>() {
+          Ok(step) if step > 0 => (range, Some(step)),
+          _ => return Err(format!("`{}` has an invalid step", part)),
+        },
+        None => (part, None),
+      };
+      let (start, end) = match range.split_once('-') {
+        _ if range == "*" => (self.min, self.max),
+        Some((start, end)) => (self.value(start)?, self.range_end(end)?),
+        None => {
+          let start = self.value(range)?;
+          (start, if step.is_some() { self.max } else { start })
+        }
+      };
+      if start > end {
+        return Err(format!("`{}` is a descending range", part));
+      }
+      for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+        mask |= 1 << value;
+      }
+    }
+    Ok(mask)
+  }
+}
+
+struct Schedule {
+  masks: [u64; 5],
+  any_day_of_month: bool,
+  any_day_of_week: bool,
+}
+
+impl Schedule {
+  fn parse(expression: &str) -> Result<Self, String> {
+    let parts: Vec<&str> = expression.split_whitespace().collect();
+    if parts.len() != FIELDS.len() {
+      return Err(format!(
+        "expected 5 fields (minute hour day-of-month month day-of-week) but found {}",
+        parts.len()
+      ));
+    }
+
+    let mut masks = [0u64; 5];
+    for ((mask, field), part) in masks.iter_mut().zip(&FIELDS).zip(&parts) {
+      *mask = field
+        .parse(part)
+        .map_err(|reason| format!("invalid {} field: {}", field.name, reason))?;
+    }
+    // Both 0 and 7 mean Sunday.
+    if masks[4] & (1 << 7) != 0 {
+      masks[4] |= 1;
+    }
+
+    Ok(Self {
+      masks,
+      any_day_of_month: parts[2].starts_with('*'),
+      any_day_of_week: parts[4].starts_with('*'),
+    })
+  }
+
+  fn matches(&self, ms: f64) -> bool {
+    let days = (ms / DAY_MS).floor() as i64;
+    let time = ms.rem_euclid(DAY_MS);
+    let minute = ((time % HOUR_MS) / 60_000.0).floor() as u32;
+    let hour = (time / HOUR_MS).floor() as u32;
+    let (month, day) = month_and_day(days);
+    // 1970-01-01 was a Thursday.
+    let weekday = (days + 4).rem_euclid(7) as u32;
+
+    let has = |field: usize, value: u32| self.masks[field] & (1 << value) != 0;
+    let day_of_month = has(2, day);
+    let day_of_week = has(4, weekday);
+    // Like Vixie cron, a day matches either field when both are restricted.
+    let day_matches = if self.any_day_of_month || self.any_day_of_week {
+      day_of_month && day_of_week
+    } else {
+      day_of_month || day_of_week
+    };
+
+    has(0, minute) && has(1, hour) && has(3, month) && day_matches
+  }
+}
+
+// Civil-from-days conversion of a day count since the Unix epoch.
+fn month_and_day(days: i64) -> (u32, u32) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let day_of_era = z - era * 146_097;
+  let year_of_era =
+    (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 {
+    shifted_month + 3
+  } else {
+    shifted_month - 9
+  };
+  (month as u32, day as u32)
+}
+
+#[wasm_bindgen]
+pub fn cron(expression: &str, time_zone: Option<String>) -> Result<String, JsValue> {
+  Schedule::parse(expression).map_err(|reason| {
+    JsValue::from_str(&format!("cron() cannot parse `{}`: {}", expression, reason))
+  })?;
+  let offset = parse_time_zone(time_zone)?;
+  Ok(format!(
+    "{}{}::{}",
+    PREFIX_CRON,
+    offset,
+    expression.split_whitespace().collect::<Vec<_>>().join(" ")
+  ))
+}
+
+fn parse_cron_key(pattern: &str) -> Option<(Schedule, i32)> {
+  let (offset, expression) = pattern.strip_prefix(PREFIX_CRON)?.split_once("::")?;
+  let offset = offset
+    .parse::<i32>()
+    .ok()
+    .filter(|offset| offset.abs() <= MAX_OFFSET_MINUTES)?;
+  Some((Schedule::parse(expression).ok()?, offset))
+}
+
+pub(crate) fn is_cron_key(pattern: &str) -> bool {
+  parse_cron_key(pattern).is_some()
+}
+
+pub(crate) fn cron_matches(pattern: &str, value: &JsValue) -> bool {
+  let Some(ms) = date_millis(value).or_else(|| value.as_f64().filter(|ms| ms.is_finite())) else {
+    return false;
+  };
+  parse_cron_key(pattern)
+    .is_some_and(|(schedule, offset)| schedule.matches(ms + f64::from(offset) * 60_000.0))
+}
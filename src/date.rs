@@ -0,0 +1,212 @@
+use crate::PREFIX_DATE;
+use js_sys::{Array, Date};
+use wasm_bindgen::prelude::*;
+
+pub(crate) const DAY_MS: f64 = 86_400_000.0;
+pub(crate) const HOUR_MS: f64 = 3_600_000.0;
+pub(crate) const MAX_OFFSET_MINUTES: i32 = 14 * 60;
+
+pub(crate) fn date_millis(value: &JsValue) -> Option<f64> {
+  value
+    .dyn_ref::<Date>()
+    .map(Date::get_time)
+    .filter(|ms| !ms.is_nan())
+}
+
+fn instant(value: &JsValue, name: &str, op: &str) -> Result<f64, JsValue> {
+  date_millis(value)
+    .or_else(|| value.as_f64().filter(|ms| ms.is_finite()))
+    .ok_or_else(|| {
+      JsValue::from_str(&format!(
+        "{}() requires `{}` to be a valid Date or epoch milliseconds",
+        op, name
+      ))
+    })
+}
+
+pub(crate) fn parse_time_zone(time_zone: Option<String>) -> Result<i32, JsValue> {
+  let Some(zone) = time_zone else {
+    return Ok(0);
+  };
+  let invalid = || {
+    JsValue::from_str(&format!(
+      "Time zone {:?} is not supported; use 'UTC' or a fixed offset such as '+05:30'",
+      zone
+    ))
+  };
+  if zone == "UTC" || zone == "Z" {
+    return Ok(0);
+  }
+
+  let (sign, rest) = if let Some(rest) = zone.strip_prefix('+') {
+    (1, rest)
+  } else if let Some(rest) = zone.strip_prefix('-') {
+    (-1, rest)
+  } else {
+    return Err(invalid());
+  };
+  let (hours, minutes) = rest.split_once(':').ok_or_else(invalid)?;
+  let part = |text: &str| {
+    (text.len() == 2 && text.chars().all(|c| c.is_ascii_digit()))
+      .then(|| text.parse::<i32>().ok())
+      .flatten()
+  };
+  let (Some(hours), Some(minutes)) = (part(hours), part(minutes)) else {
+    return Err(invalid());
+  };
+  let offset = hours * 60 + minutes;
+  if minutes >= 60 || offset > MAX_OFFSET_MINUTES {
+    return Err(invalid());
+  }
+  Ok(sign * offset)
+}
+
+fn whole_number(value: f64, max: f64, message: &str) -> Result<u32, JsValue> {
+  if value.fract() == 0.0 && (0.0..=max).contains(&value) {
+    Ok(value as u32)
+  } else {
+    Err(JsValue::from_str(message))
+  }
+}
+
+#[wasm_bindgen]
+pub fn before(date: &JsValue) -> Result<String, JsValue> {
+  let ms = instant(date, "date", "before")?;
+  Ok(format!("{}before::{}", PREFIX_DATE, ms))
+}
+
+#[wasm_bindgen]
+pub fn after(date: &JsValue) -> Result<String, JsValue> {
+  let ms = instant(date, "date", "after")?;
+  Ok(format!("{}after::{}", PREFIX_DATE, ms))
+}
+
+#[wasm_bindgen]
+pub fn between(start: &JsValue, end: &JsValue) -> Result<String, JsValue> {
+  let start = instant(start, "start", "between")?;
+  let end = instant(end, "end", "between")?;
+  if start > end {
+    return Err(JsValue::from_str(
+      "between() requires `start` to be no later than `end`",
+    ));
+  }
+  Ok(format!("{}between::{}::{}", PREFIX_DATE, start, end))
+}
+
+#[wasm_bindgen]
+pub fn weekday(days: &JsValue, time_zone: Option<String>) -> Result<String, JsValue> {
+  let days = if Array::is_array(days) {
+    days.unchecked_ref::<Array>().to_vec()
+  } else {
+    vec![days.clone()]
+  };
+  if days.is_empty() {
+    return Err(JsValue::from_str("weekday() requires at least one day"));
+  }
+
+  let mut mask = 0u8;
+  for day in days {
+    let day = whole_number(
+      day.as_f64().unwrap_or(f64::NAN),
+      6.0,
+      "weekday() days must be integers from 0 (Sunday) to 6 (Saturday)",
+    )?;
+    mask |= 1 << day;
+  }
+  let offset = parse_time_zone(time_zone)?;
+  Ok(format!("{}weekday::{}::{}", PREFIX_DATE, mask, offset))
+}
+
+#[wasm_bindgen]
+pub fn hour(from: f64, to: Option<f64>, time_zone: Option<String>) -> Result<String, JsValue> {
+  let from = whole_number(
+    from,
+    23.0,
+    "hour() requires `from` to be an integer from 0 to 23",
+  )?;
+  let to = match to {
+    Some(to) => whole_number(
+      to,
+      24.0,
+      "hour() requires `to` to be an integer from 0 to 24",
+    )?,
+    None => from + 1,
+  };
+  if from == to {
+    return Err(JsValue::from_str(
+      "hour() requires `from` and `to` to be different hours",
+    ));
+  }
+  let offset = parse_time_zone(time_zone)?;
+  Ok(format!("{}hour::{}::{}::{}", PREFIX_DATE, from, to, offset))
+}
+
+pub(crate) enum DatePattern {
+  Before(f64),
+  After(f64),
+  Between(f64, f64),
+  Weekday(u8, i32),
+  Hour(u32, u32, i32),
+}
+
+impl DatePattern {
+  pub(crate) fn parse(pattern: &str) -> Option<Self> {
+    let (op, args) = pattern.strip_prefix(PREFIX_DATE)?.split_once("::")?;
+    let args: Vec<&str> = args.split("::").collect();
+    let ms = |arg: &str| arg.parse::<f64>().ok().filter(|ms| ms.is_finite());
+    let offset = |arg: &str| {
+      arg
+        .parse::<i32>()
+        .ok()
+        .filter(|offset| offset.abs() <= MAX_OFFSET_MINUTES)
+    };
+
+    match (op, args.as_slice()) {
+      ("before", [at]) => Some(DatePattern::Before(ms(at)?)),
+      ("after", [at]) => Some(DatePattern::After(ms(at)?)),
+      ("between", [start, end]) => Some(DatePattern::Between(ms(start)?, ms(end)?)),
+      ("weekday", [mask, zone]) => {
+        let mask = mask
+          .parse::<u8>()
+          .ok()
+          .filter(|&mask| mask > 0 && mask < 128)?;
+        Some(DatePattern::Weekday(mask, offset(zone)?))
+      }
+      ("hour", [from, to, zone]) => {
+        let from = from.parse::<u32>().ok().filter(|&from| from < 24)?;
+        let to = to.parse::<u32>().ok().filter(|&to| to <= 24)?;
+        Some(DatePattern::Hour(from, to, offset(zone)?))
+      }
+      _ => None,
+    }
+  }
+
+  fn matches(&self, ms: f64) -> bool {
+    let local = |offset: i32| ms + f64::from(offset) * 60_000.0;
+    match *self {
+      DatePattern::Before(at) => ms < at,
+      DatePattern::After(at) => ms > at,
+      DatePattern::Between(start, end) => start <= ms && ms < end,
+      DatePattern::Weekday(mask, offset) => {
+        // 1970-01-01 was a Thursday.
+        let day = ((local(offset) / DAY_MS).floor() + 4.0).rem_euclid(7.0) as u8;
+        mask & (1 << day) != 0
+      }
+      DatePattern::Hour(from, to, offset) => {
+        let hour = (local(offset).rem_euclid(DAY_MS) / HOUR_MS).floor() as u32;
+        if from < to {
+          from <= hour && hour < to
+        } else {
+          hour >= from || hour < to
+        }
+      }
+    }
+  }
+}
+
+pub(crate) fn date_matches(pattern: &str, value: &JsValue) -> bool {
+  match (date_millis(value), DatePattern::parse(pattern)) {
+    (Some(ms), Some(pattern)) => pattern.matches(ms),
+    _ => false,
+  }
+}
@@ -0,0 +1,124 @@
+use crate::{
+  describe_value, encode_value, encoded_matches, MatchOptions, PatternTable, PREFIX_WHEN,
+};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+struct Coverage {
+  uncovered: Vec<JsValue>,
+  unreachable: Vec<String>,
+}
+
+fn is_predicate_key(key: &str) -> bool {
+  match key.strip_prefix(PREFIX_WHEN) {
+    Some(condition) => condition != "true" && condition != "false",
+    None => false,
+  }
+}
+
+fn analyze(domain: &Array, table: &PatternTable) -> Result<Coverage, JsValue> {
+  let mut winners: Vec<String> = Vec::new();
+  let mut uncovered = Vec::new();
+
+  for member in domain.iter() {
+    encode_value(&member)?;
+    match table.find(&member, false) {
+      Some(arm) => {
+        if !winners.contains(&arm.key) {
+          winners.push(arm.key);
+        }
+      }
+      None => uncovered.push(member),
+    }
+  }
+
+  let unreachable = Object::keys(table.patterns)
+    .iter()
+    .filter_map(|key| key.as_string())
+    .filter(|key| table.handler(key).is_some() && !is_predicate_key(key) && !winners.contains(key))
+    .collect();
+
+  Ok(Coverage {
+    uncovered,
+    unreachable,
+  })
+}
+
+fn describe_values(values: &[JsValue]) -> String {
+  values
+    .iter()
+    .map(describe_value)
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+#[wasm_bindgen(js_name = "checkExhaustive")]
+pub fn check_exhaustive(
+  domain: &Array,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<Object, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(patterns, &options);
+  table.check_collisions()?;
+  let coverage = analyze(domain, &table)?;
+
+  let result = Object::new();
+  Reflect::set(
+    &result,
+    &JsValue::from_str("exhaustive"),
+    &JsValue::from_bool(coverage.uncovered.is_empty()),
+  )?;
+  Reflect::set(
+    &result,
+    &JsValue::from_str("uncovered"),
+    &coverage.uncovered.into_iter().collect::<Array>(),
+  )?;
+  Reflect::set(
+    &result,
+    &JsValue::from_str("unreachable"),
+    &coverage
+      .unreachable
+      .iter()
+      .map(|key| JsValue::from_str(key))
+      .collect::<Array>(),
+  )?;
+  Ok(result)
+}
+
+#[wasm_bindgen(js_name = "matchExhaustive")]
+pub fn match_exhaustive(
+  value: &JsValue,
+  domain: &Array,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<JsValue, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(patterns, &options);
+  table.check_collisions()?;
+  let coverage = analyze(domain, &table)?;
+
+  if !coverage.uncovered.is_empty() {
+    return Err(JsValue::from_str(&format!(
+      "Non-exhaustive patterns. Uncovered domain members: {}",
+      describe_values(&coverage.uncovered)
+    )));
+  }
+
+  let encoded = encode_value(value)?;
+  let in_domain = domain
+    .iter()
+    .any(|member| encoded_matches(&encoded, &member, &options));
+  if !in_domain {
+    return Err(JsValue::from_str(&format!(
+      "Value {} is not a member of the domain: {}",
+      describe_value(value),
+      describe_values(&domain.to_vec())
+    )));
+  }
+
+  match table.find(value, true) {
+    Some(arm) => arm.call(value, &options),
+    None => Err(table.no_match_error(value)),
+  }
+}
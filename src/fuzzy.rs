@@ -0,0 +1,82 @@
+use crate::{MatchOptions, PREFIX_FUZZY};
+use wasm_bindgen::prelude::*;
+
+const MAX_DISTANCE: f64 = 8.0;
+
+#[wasm_bindgen]
+pub fn fuzzy(target: &str, max_distance: Option<f64>) -> Result<String, JsValue> {
+  let max_distance = max_distance.unwrap_or(1.0);
+  if max_distance.fract() != 0.0 || !(0.0..=MAX_DISTANCE).contains(&max_distance) {
+    return Err(JsValue::from_str(&format!(
+      "fuzzy() requires `maxDistance` to be an integer from 0 to {}",
+      MAX_DISTANCE
+    )));
+  }
+  if target.is_empty() {
+    return Err(JsValue::from_str("fuzzy() requires a non-empty target"));
+  }
+  Ok(format!("{}{}::{}", PREFIX_FUZZY, max_distance, target))
+}
+
+pub(crate) fn parse_fuzzy_key(pattern: &str) -> Option<(usize, &str)> {
+  let (max_distance, target) = pattern.strip_prefix(PREFIX_FUZZY)?.split_once("::")?;
+  let max_distance = max_distance
+    .parse::<usize>()
+    .ok()
+    .filter(|&max| max as f64 <= MAX_DISTANCE)?;
+  (!target.is_empty()).then_some((max_distance, target))
+}
+
+pub(crate) fn is_fuzzy_key(pattern: &str) -> bool {
+  parse_fuzzy_key(pattern).is_some()
+}
+
+// Optimal string alignment distance, given up once a whole row exceeds `max`.
+fn bounded_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+  if a.len().abs_diff(b.len()) > max {
+    return None;
+  }
+
+  let width = b.len() + 1;
+  let mut before: Vec<usize> = vec![0; width];
+  let mut previous: Vec<usize> = (0..width).collect();
+  let mut current: Vec<usize> = vec![0; width];
+  for i in 1..=a.len() {
+    current[0] = i;
+    let mut row_min = i;
+    for j in 1..width {
+      let cost = usize::from(a[i - 1] != b[j - 1]);
+      let mut distance = (previous[j] + 1)
+        .min(current[j - 1] + 1)
+        .min(previous[j - 1] + cost);
+      if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+        distance = distance.min(before[j - 2] + 1);
+      }
+      current[j] = distance;
+      row_min = row_min.min(distance);
+    }
+    if row_min > max {
+      return None;
+    }
+    std::mem::swap(&mut before, &mut previous);
+    std::mem::swap(&mut previous, &mut current);
+  }
+
+  Some(previous[b.len()]).filter(|&distance| distance <= max)
+}
+
+pub(crate) fn fuzzy_distance(
+  pattern: &str,
+  value: &JsValue,
+  options: &MatchOptions,
+) -> Option<usize> {
+  let (max_distance, target) = parse_fuzzy_key(pattern)?;
+  let value = value.as_string()?;
+  let value: Vec<char> = options.canonical(&value).chars().collect();
+  let target: Vec<char> = options.canonical(target).chars().collect();
+  bounded_distance(&value, &target, max_distance)
+}
+
+pub(crate) fn fuzzy_matches(pattern: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  fuzzy_distance(pattern, value, options).is_some()
+}
@@ -1,14 +1,65 @@
-import type { MatchValue, Options, PatternHandler, PatternMap } from './types'
+import type {
+  AsyncOptions,
+  AsyncPatternMap,
+  BestMatch,
+  BestMatchOptions,
+  ConstantOptions,
+  ConstantPatternMap,
+  ConstantTuplePatternMap,
+  Diagnostic,
+  ExhaustivenessReport,
+  MachineConfig,
+  MatchedPattern,
+  MatchValue,
+  Options,
+  PatternHandler,
+  PatternMap,
+  PhoneticAlgorithm,
+  TimeZone,
+  TuplePatternMap,
+  TypeName,
+} from './types'
 import {
+  after as _after,
   any as _any,
+  before as _before,
+  bestMatch as _bestMatch,
+  between as _between,
+  checkExhaustive as _checkExhaustive,
+  createMachine as _createMachine,
+  cron as _cron,
+  endsWith as _endsWith,
+  finite as _finite,
+  fuzzy as _fuzzy,
+  hour as _hour,
   ifLet as _ifLet,
+  includes as _includes,
+  integer as _integer,
+  length as _length,
+  lit as _lit,
   match as _match,
+  matchAll as _matchAll,
+  matchAsync as _matchAsync,
+  matchExhaustive as _matchExhaustive,
+  matchTuple as _matchTuple,
   matches as _matches,
+  maxLength as _maxLength,
+  minLength as _minLength,
+  nan as _nan,
   none as _none,
   not as _not,
+  range as _range,
   regex as _regex,
+  Router as _Router,
   some as _some,
+  soundsLike as _soundsLike,
+  startsWith as _startsWith,
+  tuple as _tuple,
+  type as _type,
+  validatePatterns as _validatePatterns,
+  weekday as _weekday,
   when as _when,
+  which as _which,
 } from './wasm/match_kit'
 
 /**
@@ -44,8 +95,10 @@ export const None: string = _none()
  * Enables custom matching logic through predicate functions or direct boolean values.
  *
  * @template T - The type of value being evaluated
- * @param condition - A predicate function that receives the value and returns a boolean,
- *                   or a boolean value for direct conditional matching
+ * @template C - The type of the `context` option passed to `match`
+ * @param condition - A predicate function that receives the value and the `context` option and returns a boolean,
+ *                   or a boolean value for direct conditional matching.
+ *                   Predicates returning a promise only match when used with `matchAsync`
  * @returns A pattern string that can be used in match expressions
  *
  * @example
@@ -55,15 +108,20 @@ export const None: string = _none()
  *   [when((n) => n < 0)]: () => 'Negative',
  *   [when(false)]: () => 'Never matches'
  * })
+ *
+ * const canEdit = when((_, ctx: { role: string }) => ctx.role === 'editor')
  * ```
  */
-export function when<T>(condition: ((value: T) => boolean) | boolean): string {
-  return _when<T>(condition)
+export function when<T, C = any>(
+  condition: ((value: T, context: C) => boolean | PromiseLike<boolean>) | boolean,
+): string {
+  return _when<T, C>(condition)
 }
 
 /**
  * Creates a pattern that matches if the value equals any of the provided values.
  * Performs equality comparison using strict equality (===) semantics.
+ * String predicates such as `startsWith` may be mixed in and match as they would on their own.
  *
  * @param values - Variable number of values to match against
  * @returns A pattern string that matches any of the specified values
@@ -83,6 +141,7 @@ export function any(...values: MatchValue[]): string {
 /**
  * Creates a pattern that matches if the value does NOT equal any of the provided values.
  * Performs negated equality comparison using strict inequality (!==) semantics.
+ * String predicates such as `startsWith` may be mixed in to exclude every string they match.
  *
  * @param values - Variable number of values to exclude from matching
  * @returns A pattern string that matches values not in the exclusion list
@@ -119,9 +178,360 @@ export function regex(pattern: string, flags?: string): string {
   return _regex(pattern, flags)
 }
 
+/**
+ * Creates a pattern that matches strings within a bounded edit distance of `target`. Insertions, deletions,
+ * substitutions and swaps of adjacent characters each count as one edit. The handler receives the distance as
+ * its third argument, after the value and context.
+ * Honours the `caseSensitive` and `normalize` options, and is tried after regex and wildcard patterns.
+ *
+ * @param target - The string to compare against
+ * @param maxDistance - The largest number of edits accepted, from 0 to 8 (default: 1)
+ * @returns A pattern string that matches near misses of `target`
+ * @throws {Error} If `target` is empty or `maxDistance` is out of range
+ *
+ * @example
+ * ```typescript
+ * match('deplyo', {
+ *   [fuzzy('deploy', 2)]: (_value, _context, distance) => (distance === 0 ? 'Deploying' : 'Did you mean deploy?'),
+ *   _: () => 'Unknown command'
+ * })
+ * ```
+ */
+export function fuzzy(target: string, maxDistance?: number): string {
+  return _fuzzy(target, maxDistance)
+}
+
+/**
+ * Creates a pattern that matches strings pronounced like `name`, such as 'Smyth' for 'Smith'.
+ * `name` is encoded once, when the pattern is created; each value is encoded when it is matched, and matches when
+ * it shares a code with `name`. Double Metaphone yields a primary and an alternate code, so it also links spellings
+ * such as 'Schmidt' and 'Smith'; Soundex yields one letter and three digits.
+ * Phonetic patterns are tried after fuzzy patterns.
+ *
+ * @param name - The name to compare against
+ * @param algorithm - 'doubleMetaphone' (default) or 'soundex'
+ * @returns A pattern string that matches names that sound alike
+ * @throws {Error} If the algorithm is unknown or `name` has no letters
+ *
+ * @example
+ * ```typescript
+ * match(customer.lastName, {
+ *   [soundsLike('Smith')]: () => 'Possible duplicate',
+ *   [soundsLike('Robert', 'soundex')]: () => 'Same Soundex code as Robert',
+ *   _: () => 'New customer'
+ * })
+ * ```
+ */
+export function soundsLike(name: string, algorithm?: PhoneticAlgorithm): string {
+  return _soundsLike(name, algorithm)
+}
+
+/**
+ * Creates a pattern that matches numbers and bigints between `min` and `max`, inclusive.
+ * Numbers and bigints are compared by value, so `range(0n, 10)` matches both `5` and `5n`.
+ * Honours the `coerce` option.
+ *
+ * @param min - The lower bound, a number or bigint (use `-Infinity` for no lower bound)
+ * @param max - The upper bound, a number or bigint (use `Infinity` for no upper bound)
+ * @returns A pattern string that matches values within the bounds
+ * @throws {Error} If a bound is `NaN` or not numeric, or if `min` is greater than `max`
+ *
+ * @example
+ * ```typescript
+ * match(balance, {
+ *   [range(-Infinity, -1n)]: () => 'Overdrawn',
+ *   [range(0n, 100n)]: () => 'Low',
+ *   _: () => 'Healthy'
+ * })
+ * ```
+ */
+export function range(min: number | bigint, max: number | bigint): string {
+  return _range(min, max)
+}
+
+/**
+ * Creates a pattern that matches `Date` values strictly earlier than `date`.
+ *
+ * @param date - A `Date` or epoch milliseconds
+ * @returns A pattern string that matches earlier dates
+ * @throws {Error} If `date` is not a valid `Date` or finite number
+ */
+export function before(date: Date | number): string {
+  return _before(date)
+}
+
+/**
+ * Creates a pattern that matches `Date` values strictly later than `date`.
+ *
+ * @param date - A `Date` or epoch milliseconds
+ * @returns A pattern string that matches later dates
+ * @throws {Error} If `date` is not a valid `Date` or finite number
+ */
+export function after(date: Date | number): string {
+  return _after(date)
+}
+
+/**
+ * Creates a pattern that matches `Date` values from `start` (inclusive) up to `end` (exclusive).
+ *
+ * @param start - The first matching instant, as a `Date` or epoch milliseconds
+ * @param end - The first instant after the window, as a `Date` or epoch milliseconds
+ * @returns A pattern string that matches dates within the window
+ * @throws {Error} If either bound is invalid or `start` is later than `end`
+ *
+ * @example
+ * ```typescript
+ * match(now, {
+ *   [between(saleStart, saleEnd)]: () => 'Sale price',
+ *   _: () => 'Regular price'
+ * })
+ * ```
+ */
+export function between(start: Date | number, end: Date | number): string {
+  return _between(start, end)
+}
+
+/**
+ * Creates a pattern that matches `Date` values falling on the given days of the week.
+ *
+ * @param days - A day or days, from 0 (Sunday) to 6 (Saturday)
+ * @param timeZone - `'UTC'` (default) or a fixed offset such as `'+05:30'`
+ * @returns A pattern string that matches dates on those days
+ * @throws {Error} If a day is out of range or the time zone is not supported
+ *
+ * @example
+ * ```typescript
+ * match(now, {
+ *   [weekday([0, 6], '+01:00')]: () => 'Weekend',
+ *   _: () => 'Weekday'
+ * })
+ * ```
+ */
+export function weekday(days: number | number[], timeZone?: TimeZone): string {
+  return _weekday(days, timeZone)
+}
+
+/**
+ * Creates a pattern that matches `Date` values whose hour of day is from `from` up to, but not including, `to`.
+ * When `to` is smaller than `from` the window wraps past midnight, so `hour(22, 6)` matches night hours.
+ *
+ * @param from - The first matching hour, from 0 to 23
+ * @param to - The hour the window ends at, from 0 to 24 (default: `from + 1`)
+ * @param timeZone - `'UTC'` (default) or a fixed offset such as `'-08:00'`
+ * @returns A pattern string that matches dates within those hours
+ * @throws {Error} If an hour is out of range, the hours are equal, or the time zone is not supported
+ *
+ * @example
+ * ```typescript
+ * match(now, {
+ *   [hour(9, 17, '-05:00')]: () => 'Office hours',
+ *   _: () => 'After hours'
+ * })
+ * ```
+ */
+export function hour(from: number, to?: number, timeZone?: TimeZone): string {
+  return _hour(from, to, timeZone)
+}
+
+/**
+ * Creates a pattern that matches `Date` values and epoch milliseconds that fall within a cron schedule.
+ * The expression has five fields: minute, hour, day of month, month and day of week. Fields accept `*`, numbers,
+ * ranges (`9-17`), lists (`1,15`) and steps (`*\/15`); months and days also accept names such as `JAN` or `MON`.
+ * As in Vixie cron, when both day fields are restricted a date matches if either of them does.
+ *
+ * @param expression - A five-field cron expression
+ * @param timeZone - `'UTC'` (default) or a fixed offset such as `'+05:30'`
+ * @returns A pattern string that matches instants within the schedule
+ * @throws {Error} If the expression cannot be parsed or the time zone is not supported
+ *
+ * @example
+ * ```typescript
+ * match(ticket.createdAt, {
+ *   [cron('* 9-17 * * MON-FRI', '+01:00')]: () => 'Support desk',
+ *   _: () => 'On-call'
+ * })
+ * ```
+ */
+export function cron(expression: string, timeZone?: TimeZone): string {
+  return _cron(expression, timeZone)
+}
+
+/**
+ * Creates an exact pattern that only matches values of the same type.
+ * Plain keys are strings, so the key `1` also matches the number `1`; `lit('1')` and `lit(1)` tell them apart.
+ * Combine with the `strict` option to stop plain keys from matching non-string values.
+ *
+ * @param value - The value to match exactly
+ * @returns A pattern string that matches the value only when its type agrees
+ *
+ * @example
+ * ```typescript
+ * match(payload.id, {
+ *   [lit(1)]: () => 'Numeric id',
+ *   [lit('1')]: () => 'String id',
+ * }, { strict: true })
+ * ```
+ */
+export function lit(value: MatchValue): string {
+  return _lit(value)
+}
+
+/**
+ * Creates a pattern that matches strings starting with the given prefix.
+ * Honours the `caseSensitive` option, and can be passed to `any` and `not`.
+ *
+ * @param prefix - The required prefix
+ * @returns A pattern string that matches strings with that prefix
+ *
+ * @example
+ * ```typescript
+ * match(path, {
+ *   [startsWith('/api/')]: () => 'API route',
+ *   _: () => 'Page'
+ * })
+ * ```
+ */
+export function startsWith(prefix: string): string {
+  return _startsWith(prefix)
+}
+
+/**
+ * Creates a pattern that matches strings ending with the given suffix.
+ * Honours the `caseSensitive` option, and can be passed to `any` and `not`.
+ *
+ * @param suffix - The required suffix
+ * @returns A pattern string that matches strings with that suffix
+ */
+export function endsWith(suffix: string): string {
+  return _endsWith(suffix)
+}
+
+/**
+ * Creates a pattern that matches strings containing the given substring.
+ * Honours the `caseSensitive` option, and can be passed to `any` and `not`.
+ *
+ * @param substring - The required substring
+ * @returns A pattern string that matches strings containing it
+ */
+export function includes(substring: string): string {
+  return _includes(substring)
+}
+
+/**
+ * Creates a pattern that matches strings whose `length` is at least `length`.
+ *
+ * @param length - The minimum length, a non-negative integer
+ * @returns A pattern string that matches long enough strings
+ * @throws {Error} If the length is negative or not an integer
+ */
+export function minLength(length: number): string {
+  return _minLength(length)
+}
+
+/**
+ * Creates a pattern that matches strings whose `length` is at most `length`.
+ *
+ * @param length - The maximum length, a non-negative integer
+ * @returns A pattern string that matches short enough strings
+ * @throws {Error} If the length is negative or not an integer
+ */
+export function maxLength(length: number): string {
+  return _maxLength(length)
+}
+
+/**
+ * Creates a pattern that matches strings whose `length` is exactly `length`.
+ *
+ * @param length - The required length, a non-negative integer
+ * @returns A pattern string that matches strings of that length
+ * @throws {Error} If the length is negative or not an integer
+ *
+ * @example
+ * ```typescript
+ * match(code, {
+ *   [length(2)]: () => 'ISO 3166-1 alpha-2',
+ *   [length(3)]: () => 'ISO 3166-1 alpha-3',
+ *   _: () => 'Unknown format'
+ * })
+ * ```
+ */
+export function length(length: number): string {
+  return _length(length)
+}
+
+/**
+ * Creates a pattern that matches values of the given type, checked natively without calling into JavaScript.
+ * Type patterns have lower priority than every other pattern except the default handler `_`.
+ *
+ * @param name - One of 'string', 'number', 'bigint', 'boolean', 'date', 'null' or 'undefined'
+ * @returns A pattern string that matches values of that type
+ * @throws {Error} If the type name is not supported
+ *
+ * @example
+ * ```typescript
+ * match(input, {
+ *   [type('string')]: () => 'Text',
+ *   [type('number')]: () => 'Number',
+ *   _: () => 'Other'
+ * })
+ * ```
+ */
+export function type(name: TypeName): string {
+  return _type(name)
+}
+
+/**
+ * Creates a pattern that matches integral numbers, like `Number.isInteger`.
+ *
+ * @returns A pattern string that matches integers
+ */
+export function integer(): string {
+  return _integer()
+}
+
+/**
+ * Creates a pattern that matches numbers other than `NaN`, `Infinity` and `-Infinity`, like `Number.isFinite`.
+ *
+ * @returns A pattern string that matches finite numbers
+ */
+export function finite(): string {
+  return _finite()
+}
+
+/**
+ * Creates a pattern that matches `NaN`. Equivalent to `lit(NaN)`, but reads better alongside other type patterns.
+ *
+ * @returns A pattern string that matches `NaN`
+ */
+export function nan(): string {
+  return _nan()
+}
+
+/**
+ * Creates a pattern that matches a tuple of values position by position, for use with `matchTuple`.
+ * Each position is either a plain value, compared like an `any` value, or a pattern created with
+ * `any`, `not`, `regex`, `when`, `type`, `Some`, `None`, a wildcard string, or `_` to accept any value.
+ *
+ * @param positions - One pattern or value per tuple position
+ * @returns A pattern string that can be used as a key in `matchTuple`
+ *
+ * @example
+ * ```typescript
+ * matchTuple([state, event], {
+ *   [tuple('idle', any('start', 'resume'))]: () => 'running',
+ *   [tuple('running', 'stop')]: () => 'idle',
+ *   [tuple('_', 'error')]: () => 'failed',
+ * })
+ * ```
+ */
+export function tuple(...positions: MatchValue[]): string {
+  return _tuple(positions)
+}
+
 /**
  * Performs exhaustive pattern matching against a value and returns the result of the first matching pattern handler.
  * All possible cases must be covered in the pattern map to ensure exhaustiveness.
+ * To have coverage verified against a finite set of allowed values, use `matchExhaustive` instead.
  * For single-case matching or when you only care about specific patterns, use `ifLet` instead.
  *
  * @template R - The return type of the pattern handlers
@@ -129,6 +539,13 @@ export function regex(pattern: string, flags?: string): string {
  * @param patterns - A mapping of patterns to their corresponding handler functions
  * @param options - Optional configuration object for matching behavior
  * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.constants - Whether non-function pattern values are returned as-is instead of being skipped (default: false)
+ * @param options.context - Passed to `when` predicates and handlers as their second argument, after the value
+ * @param options.strict - Whether plain keys only match string values, leaving other types to `lit` keys (default: false)
+ * @param options.coerce - Whether trimmed numeric strings and 'true'/'false' are converted before exact, `lit`, `any` and `not` comparisons (default: false)
+ * @param options.equality - How `lit`, `any` and `not` compare numbers: 'sameValueZero' or 'sameValue' (default: 'sameValueZero')
+ * @param options.locale - A language tag; 'tr' and 'az' enable Turkic case folding when `caseSensitive` is false
+ * @param options.normalize - Trim, Unicode normalization, whitespace collapsing and diacritic stripping applied to values and non-regex patterns
  * @returns The result returned by the matching pattern handler
  * @throws {Error} If no pattern matches and patterns are not exhaustive
  *
@@ -139,12 +556,170 @@ export function regex(pattern: string, flags?: string): string {
  *   [when((code) => code >= 400 && code < 500)]: () => 'Client Error',
  *   [when((code) => code >= 500)]: () => 'Server Error'
  * })
+ *
+ * const label = match(httpStatus, { 200: 'OK', 404: 'Not Found', _: 'Unknown' }, { constants: true })
  * ```
  */
-export function match<R>(value: MatchValue, patterns: PatternMap<R>, options?: Options): R {
+export function match<R>(value: MatchValue, patterns: PatternMap<R>, options?: Options): R
+export function match<R>(
+  value: MatchValue,
+  patterns: ConstantPatternMap<R>,
+  options: ConstantOptions,
+): R
+export function match<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): R {
   return _match(value, patterns, options)
 }
 
+/**
+ * Performs pattern matching like `match`, but awaits `when` predicates and handlers that return promises.
+ * Predicates are awaited one at a time, in pattern priority order; a rejected predicate counts as not matching.
+ * With `predicates: 'concurrent'` all predicates are started up front, but the highest-priority match still wins.
+ *
+ * @template R - The resolved type of the pattern handlers
+ * @param value - The value to evaluate against patterns
+ * @param patterns - A mapping of patterns to their corresponding, possibly async, handler functions
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.context - Passed to `when` predicates and handlers as their second argument, after the value
+ * @param options.signal - An `AbortSignal` that cancels matching while a predicate or handler is pending
+ * @param options.timeout - Maximum time in milliseconds to wait for each `when` predicate
+ * @param options.predicates - Whether predicates run 'sequential'ly (default) or 'concurrent'ly
+ * @returns A promise resolving to the awaited result of the matching pattern handler
+ * @throws {Error} The promise rejects if no pattern matches, or if the matching handler rejects
+ * @throws {MatchAbortError} The promise rejects if the signal is aborted or a predicate times out
+ *
+ * @example
+ * ```typescript
+ * const page = await matchAsync(route, {
+ *   [when(async () => await db.hasPermission(user, 'admin'))]: () => loadAdminPage(),
+ *   [when(async () => await db.hasPermission(user, 'editor'))]: () => loadEditorPage(),
+ *   _: () => 'Forbidden',
+ * }, { signal: AbortSignal.timeout(1000) })
+ * ```
+ */
+export function matchAsync<R>(
+  value: MatchValue,
+  patterns: AsyncPatternMap<R>,
+  options?: AsyncOptions,
+): Promise<R> {
+  return _matchAsync(value, patterns, options)
+}
+
+/**
+ * Runs the handler of every pattern that matches the value, instead of only the first one.
+ * Handlers are called in the same priority order that `match` uses to pick a winner.
+ * The default handler `_` only runs when no other pattern matches.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param value - The value to evaluate against patterns
+ * @param patterns - A mapping of patterns to their corresponding handler functions
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.context - Passed to `when` predicates and handlers as their second argument, after the value
+ * @returns The results of all matching handlers in priority order, or an empty array if nothing matched
+ *
+ * @example
+ * ```typescript
+ * const tags = matchAll(fileName, {
+ *   [regex('\\.tsx?$')]: () => 'typescript',
+ *   [regex('\\.test\\.')]: () => 'test',
+ *   'src/*': () => 'source',
+ *   _: () => 'other',
+ * })
+ * // 'src/app.test.ts' => ['typescript', 'test', 'source']
+ * ```
+ */
+export function matchAll<R>(value: MatchValue, patterns: PatternMap<R>, options?: Options): R[]
+export function matchAll<R>(
+  value: MatchValue,
+  patterns: ConstantPatternMap<R>,
+  options: ConstantOptions,
+): R[]
+export function matchAll<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): R[] {
+  return _matchAll(value, patterns, options)
+}
+
+/**
+ * Scores every pattern that matches the value and runs the handler of the best one, instead of the first one in
+ * priority order. By default exact matches score 1, `fuzzy()` matches score between 0.5 and 1 by distance,
+ * wildcards score between 0.1 and 0.5 by their share of literal characters, `some()` and type patterns score 0.1,
+ * other patterns score 0.5 and `_` scores 0.
+ * Ties go to the pattern `match` would try first.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param value - The value to evaluate against patterns
+ * @param patterns - A mapping of patterns to their corresponding handler functions
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.context - Passed to `when` predicates and handlers as their second argument, after the value
+ * @param options.score - Computes the score of each candidate instead of the defaults; it receives the value and the candidate's key, kind, default score and distance
+ * @returns The handler's result with the key, kind and score of the winning pattern, and its distance for `fuzzy()`
+ * @throws {Error} If no pattern matches and there is no default handler, or if `score` does not return a finite number
+ *
+ * @example
+ * ```typescript
+ * const { result, score } = bestMatch('deplyo', {
+ *   'dep*': () => 'deployments',
+ *   [fuzzy('deploy', 2)]: () => 'deploy',
+ *   _: () => 'unknown',
+ * })
+ * // result: 'deploy', score: 0.833...
+ * ```
+ */
+export function bestMatch<R>(
+  value: MatchValue,
+  patterns: PatternMap<R>,
+  options?: BestMatchOptions,
+): BestMatch<R>
+export function bestMatch<R>(
+  value: MatchValue,
+  patterns: ConstantPatternMap<R>,
+  options: BestMatchOptions & { constants: true },
+): BestMatch<R>
+export function bestMatch<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: BestMatchOptions,
+): BestMatch<R> {
+  return _bestMatch(value, patterns, options)
+}
+
+/**
+ * Determines which pattern would win for a value, without executing any handlers.
+ * Uses the same priority rules as `match`.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param value - The value to evaluate against patterns
+ * @param patterns - A mapping of patterns to their corresponding handler functions
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @returns The key and kind of the winning pattern, or undefined if only the default handler `_` or nothing applies
+ *
+ * @example
+ * ```typescript
+ * which('hello', {
+ *   [any('hi', 'hey')]: () => 'greeting',
+ *   'h*o': () => 'wildcard',
+ * })
+ * // { key: 'h*o', kind: 'wildcard' }
+ * ```
+ */
+export function which<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): MatchedPattern | undefined {
+  return _which(value, patterns, options)
+}
+
 /**
  * Conditionally executes a handler if the value matches the specified pattern.
  * Returns the handler result if matched, otherwise returns undefined.
@@ -179,6 +754,7 @@ export function ifLet<R>(
  * @param pattern - The pattern to evaluate
  * @param options - Optional configuration object for matching behavior
  * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.context - Passed to `when` predicates as their second argument, after the value
  * @returns true if the value matches the pattern, false otherwise
  *
  * @example
@@ -193,3 +769,276 @@ export function ifLet<R>(
 export function matches(value: MatchValue, pattern: MatchValue, options?: Options): boolean {
   return _matches(value, pattern, options)
 }
+
+/**
+ * Statically inspects a pattern map without matching any value.
+ * Reports invalid regular expressions or flags, malformed pattern keys, `any` values repeated
+ * across arms, arms made unreachable by `not` or `when(true)`, and handlers that are not functions.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param patterns - The pattern map to inspect
+ * @param options - Optional configuration object, interpreted the same way as in `match`
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @returns A list of diagnostics, empty when no problems were found
+ *
+ * @example
+ * ```typescript
+ * const diagnostics = validatePatterns({
+ *   [regex('(unclosed')]: () => 'never compiles',
+ *   [when(true)]: () => 'always',
+ *   _: () => 'unreachable',
+ * })
+ * // [{ severity: 'error', code: 'invalid-regex', ... }, { severity: 'warning', code: 'shadowed', key: '_', ... }]
+ * ```
+ */
+export function validatePatterns<R>(
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): Diagnostic[] {
+  return _validatePatterns(patterns, options)
+}
+
+/**
+ * Checks a pattern map against a finite domain of allowed values without calling any handler.
+ * Reports domain members that no arm matches and arms that match no domain member.
+ * Exact, `any`, `not`, `regex`, wildcard, `Some`/`None`, `when(true)` and `_` arms are taken into account;
+ * `when` predicate functions are not called, so they neither cover members nor get reported.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param domain - The complete list of values the matched value may take
+ * @param patterns - The pattern map to check
+ * @param options - Optional configuration object, interpreted the same way as in `match`
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @returns A report with the uncovered domain members and the unreachable arm keys
+ *
+ * @example
+ * ```typescript
+ * checkExhaustive(['red', 'green', 'blue'], {
+ *   [any('red', 'green')]: () => 'warm-ish',
+ *   yellow: () => 'never used',
+ * })
+ * // { exhaustive: false, uncovered: ['blue'], unreachable: ['yellow'] }
+ * ```
+ */
+export function checkExhaustive<R>(
+  domain: readonly MatchValue[],
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): ExhaustivenessReport {
+  return _checkExhaustive(domain, patterns, options)
+}
+
+/**
+ * Performs pattern matching like `match`, after verifying that the patterns cover every member of the domain.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param value - The value to evaluate against patterns, which must be a member of the domain
+ * @param domain - The complete list of values the matched value may take
+ * @param patterns - A mapping of patterns to their corresponding handler functions
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @returns The result returned by the matching pattern handler
+ * @throws {Error} If some domain member is not covered, or if the value is not a member of the domain
+ *
+ * @example
+ * ```typescript
+ * type Light = 'red' | 'yellow' | 'green'
+ * const lights: Light[] = ['red', 'yellow', 'green']
+ *
+ * const action = matchExhaustive(light, lights, {
+ *   red: () => 'Stop',
+ *   yellow: () => 'Slow down',
+ *   green: () => 'Go',
+ * })
+ * ```
+ */
+export function matchExhaustive<R>(
+  value: MatchValue,
+  domain: readonly MatchValue[],
+  patterns: PatternMap<R>,
+  options?: Options,
+): R
+export function matchExhaustive<R>(
+  value: MatchValue,
+  domain: readonly MatchValue[],
+  patterns: ConstantPatternMap<R>,
+  options: ConstantOptions,
+): R
+export function matchExhaustive<R>(
+  value: MatchValue,
+  domain: readonly MatchValue[],
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): R {
+  return _matchExhaustive(value, domain, patterns, options)
+}
+
+/**
+ * Matches several values at once against patterns created with `tuple`.
+ * An arm matches only when every position matches; arms are tried in the order they are declared,
+ * and the default handler `_` runs when none of them match.
+ *
+ * @template R - The return type of the pattern handlers
+ * @param values - The values to evaluate, one per tuple position
+ * @param patterns - A mapping of `tuple` patterns to their corresponding handler functions, which receive the values array
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.constants - Whether non-function pattern values are returned as-is instead of being skipped (default: false)
+ * @param options.context - Passed to `when` predicates and handlers as their second argument
+ * @returns The result returned by the matching pattern handler
+ * @throws {Error} If a key was not created with `tuple`, if a pattern's length differs from the number of values,
+ *                 or if no pattern matches
+ *
+ * @example
+ * ```typescript
+ * const next = matchTuple([light, event], {
+ *   [tuple('red', 'timer')]: () => 'green',
+ *   [tuple('green', 'timer')]: () => 'yellow',
+ *   [tuple('yellow', 'timer')]: () => 'red',
+ *   [tuple('_', 'fault')]: () => 'blinking',
+ *   _: ([current]) => current,
+ * })
+ * ```
+ */
+export function matchTuple<R>(
+  values: readonly MatchValue[],
+  patterns: TuplePatternMap<R>,
+  options?: Options,
+): R
+export function matchTuple<R>(
+  values: readonly MatchValue[],
+  patterns: ConstantTuplePatternMap<R>,
+  options: ConstantOptions,
+): R
+export function matchTuple<R>(
+  values: readonly MatchValue[],
+  patterns: TuplePatternMap<R> | ConstantTuplePatternMap<R>,
+  options?: Options,
+): R {
+  return _matchTuple(values, patterns, options)
+}
+
+/**
+ * A finite state machine created by `createMachine`.
+ *
+ * @template S - The names of the machine's states
+ */
+export type Machine<S extends string = string> = {
+  /**
+   * The current state.
+   */
+  readonly state: S
+  /**
+   * Whether some transition, including its `when` guards, accepts the event in the current state.
+   */
+  can(event: MatchValue): boolean
+  /**
+   * Moves to the target of the first matching transition, running the exit action of the current state
+   * and then the entry action of the target state.
+   *
+   * @returns The new state
+   * @throws {MachineError} If no transition matches, or if the target is not a declared state
+   */
+  send(event: MatchValue): S
+  /**
+   * Releases the memory held by the machine.
+   */
+  free(): void
+}
+
+/**
+ * Creates a finite state machine whose transitions are declared as `tuple(statePattern, eventPattern)` keys.
+ * State and event positions accept every pattern `tuple` supports, so `any`, `not`, wildcards and `_`
+ * can share one transition between several states or events, and `when` positions act as guards.
+ * Transitions are tried in declaration order, as in `matchTuple`.
+ *
+ * @template S - The names of the machine's states
+ * @template C - The type of the `context` option
+ * @param config - The machine definition
+ * @param config.states - The declared states, each with optional `entry` and `exit` actions
+ * @param config.transitions - A mapping of `tuple` patterns to a target state name or a function returning one
+ * @param config.initial - The starting state (default: the first declared state)
+ * @param config.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param config.context - Passed to `when` guards, function targets and actions as their second argument
+ * @returns A machine starting in the initial state; the initial state's entry action is not run
+ * @throws {MachineError} If the configuration is invalid or a transition targets an undeclared state
+ *
+ * @example
+ * ```typescript
+ * const door = createMachine({
+ *   states: {
+ *     closed: {},
+ *     open: { entry: () => console.log('opened') },
+ *     locked: {},
+ *   },
+ *   transitions: {
+ *     [tuple('closed', 'open')]: 'open',
+ *     [tuple('open', 'close')]: 'closed',
+ *     [tuple('closed', 'lock')]: 'locked',
+ *     [tuple('locked', when((_, ctx) => ctx.hasKey))]: 'closed',
+ *   },
+ *   context: { hasKey: true },
+ * })
+ *
+ * door.send('open') // 'open'
+ * door.send('lock') // throws MachineError with code 'invalid-transition'
+ * ```
+ */
+export function createMachine<S extends string, C = any>(config: MachineConfig<S, C>): Machine<S> {
+  return _createMachine(config) as Machine<S>
+}
+
+/**
+ * Dispatches values to handlers subscribed by pattern, for message buses and event emitters.
+ * Handlers can be added and removed at any time, and several handlers may share one pattern.
+ * A dispatch picks the winning pattern with the same priority rules as `match`, then calls every
+ * handler subscribed to it in subscription order.
+ *
+ * @template R - The return type of the handlers
+ * @template C - The type of the dispatch context
+ * @param options - Optional configuration object for matching behavior
+ * @param options.caseSensitive - Whether string comparisons should be case-sensitive (default: true)
+ * @param options.context - The default context passed to `when` predicates and handlers
+ *
+ * @example
+ * ```typescript
+ * const bus = new Router<void, { userId: string }>()
+ *
+ * const id = bus.on('order.*', (topic, ctx) => audit(topic, ctx.userId))
+ * bus.on(any('order.paid', 'order.refunded'), (topic) => notifyBilling(topic))
+ *
+ * bus.dispatch('order.paid', { userId: 'u1' }) // calls notifyBilling only
+ * bus.off(id)
+ * ```
+ */
+export class Router<R = any, C = any> extends _Router<R, C> {
+  /**
+   * Subscribes a handler to a pattern of any kind.
+   *
+   * @returns A subscription id to pass to `off`
+   * @throws {Error} If called from a `when` predicate while a dispatch is matching
+   */
+  on(pattern: string, handler: PatternHandler<R, C>): number {
+    return super.on(pattern, handler)
+  }
+
+  /**
+   * Removes a subscription. The pattern itself is dropped once its last handler is removed.
+   *
+   * @returns true if the subscription existed
+   */
+  off(id: number): boolean {
+    return super.off(id)
+  }
+
+  /**
+   * Calls every handler of the pattern that wins for the value.
+   *
+   * @param value - The value to route
+   * @param context - Passed to `when` predicates and handlers, instead of the `context` option
+   * @returns The handler results in subscription order, or an empty array if no pattern matched
+   */
+  dispatch(value: MatchValue, context?: C): R[] {
+    return super.dispatch(value, context)
+  }
+}
@@ -1,8 +1,36 @@
-use js_sys::{Function, Object, Reflect, RegExp};
+use js_sys::{Array, Function, JsString, Object, Reflect, RegExp};
+use std::borrow::Cow;
+use std::cell::OnceCell;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+use std::ops::ControlFlow;
 use wasm_bindgen::prelude::*;
 
+mod async_match;
+mod best_match;
+mod cron;
+mod date;
+mod exhaustive;
+mod fuzzy;
+mod machine;
+mod phonetic;
+mod router;
+mod tuple;
+mod validate;
+
+pub use async_match::match_async;
+pub use best_match::best_match;
+pub use cron::cron;
+pub use date::{after, before, between, hour, weekday};
+pub use exhaustive::{check_exhaustive, match_exhaustive};
+pub use fuzzy::fuzzy;
+pub use machine::{create_machine, Machine};
+pub use phonetic::sounds_like;
+pub use router::Router;
+pub use tuple::{match_tuple, tuple};
+pub use validate::validate_patterns;
+
 const SOME_VALUE: &str = "__SOME__";
 const NONE_VALUE: &str = "__NONE__";
 const DEFAULT_HANDLER: &str = "_";
@@ -11,6 +39,14 @@ const PREFIX_WHEN: &str = "when::";
 const PREFIX_ANY: &str = "any::";
 const PREFIX_NOT: &str = "not::";
 const PREFIX_REGEX: &str = "regex::";
+const PREFIX_TYPE: &str = "type::";
+const PREFIX_TEXT: &str = "str::";
+const PREFIX_LIT: &str = "lit::";
+const PREFIX_RANGE: &str = "range::";
+const PREFIX_DATE: &str = "date::";
+const PREFIX_CRON: &str = "cron::";
+const PREFIX_FUZZY: &str = "fuzzy::";
+const PREFIX_PHONETIC: &str = "sound::";
 const SEP: char = '\x1F';
 
 #[wasm_bindgen]
@@ -26,7 +62,9 @@ pub fn none() -> String {
 pub enum JsType {
   String,
   Number,
+  BigInt,
   Boolean,
+  Date,
   Null,
   Undefined,
   Unknown,
@@ -38,8 +76,12 @@ fn js_typeof(value: &JsValue) -> JsType {
     JsType::String
   } else if value.as_f64().is_some() {
     JsType::Number
+  } else if value.is_bigint() {
+    JsType::BigInt
   } else if value.as_bool().is_some() {
     JsType::Boolean
+  } else if value.is_instance_of::<js_sys::Date>() {
+    JsType::Date
   } else if value.is_null() {
     JsType::Null
   } else if value.is_undefined() {
@@ -110,14 +152,96 @@ fn encode_value(value: &JsValue) -> Result<String, JsValue> {
     JsType::Undefined => format!("undefined{}", SEP),
     JsType::Null => format!("null{}", SEP),
     JsType::String => format!("string{}{}", SEP, value.as_string().unwrap_or_default()),
-    JsType::Number => format!("number{}{}", SEP, value.as_f64().unwrap_or(0.0)),
+    JsType::Number => format!(
+      "number{}{}",
+      SEP,
+      encode_number(value.as_f64().unwrap_or(0.0))
+    ),
+    JsType::BigInt => format!("bigint{}{}", SEP, bigint_to_string(value)),
     JsType::Boolean => format!("boolean{}{}", SEP, value.as_bool().unwrap_or(false)),
+    JsType::Date => format!(
+      "date{}{}",
+      SEP,
+      encode_number(value.unchecked_ref::<js_sys::Date>().get_time())
+    ),
     JsType::Unknown => return Err(JsValue::from_str("Unsupported value type")),
   };
   Ok(encoded)
 }
 
-fn compare_encoded_value(encoded: &str, value: &JsValue, case_sensitive: bool) -> bool {
+fn decode_value(encoded: &str) -> Option<JsValue> {
+  let (val_type, val_str) = encoded.split_once(SEP)?;
+  match val_type {
+    "undefined" if val_str.is_empty() => Some(JsValue::UNDEFINED),
+    "null" if val_str.is_empty() => Some(JsValue::NULL),
+    "string" => Some(JsValue::from_str(val_str)),
+    "number" => val_str.parse::<f64>().ok().map(JsValue::from_f64),
+    "bigint" if is_decimal_integer(val_str) => js_sys::BigInt::new(&JsValue::from_str(val_str))
+      .ok()
+      .map(JsValue::from),
+    "boolean" => val_str.parse::<bool>().ok().map(JsValue::from_bool),
+    "date" => val_str
+      .parse::<f64>()
+      .ok()
+      .map(|ms| js_sys::Date::new(&JsValue::from_f64(ms)).into()),
+    _ => None,
+  }
+}
+
+fn is_decimal_integer(text: &str) -> bool {
+  let digits = text.strip_prefix('-').unwrap_or(text);
+  !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn bigint_to_string(value: &JsValue) -> String {
+  value
+    .unchecked_ref::<js_sys::BigInt>()
+    .to_string(10)
+    .map(String::from)
+    .unwrap_or_default()
+}
+
+fn number_to_string(number: f64) -> String {
+  if number.is_nan() {
+    "NaN".to_string()
+  } else if number.is_infinite() {
+    if number > 0.0 {
+      "Infinity"
+    } else {
+      "-Infinity"
+    }
+    .to_string()
+  } else if number == 0.0 {
+    "0".to_string()
+  } else if (1e-6..1e21).contains(&number.abs()) {
+    number.to_string()
+  } else {
+    js_sys::Number::from(number)
+      .to_string(10)
+      .map(String::from)
+      .unwrap_or_else(|_| number.to_string())
+  }
+}
+
+fn encode_number(number: f64) -> String {
+  if number == 0.0 && number.is_sign_negative() {
+    "-0".to_string()
+  } else {
+    number_to_string(number)
+  }
+}
+
+fn numbers_equal(a: f64, b: f64, same_value: bool) -> bool {
+  if a.is_nan() || b.is_nan() {
+    a.is_nan() && b.is_nan()
+  } else if same_value && a == 0.0 && b == 0.0 {
+    a.is_sign_negative() == b.is_sign_negative()
+  } else {
+    a == b
+  }
+}
+
+fn compare_encoded_value(encoded: &str, value: &JsValue, options: &MatchOptions) -> bool {
   if !encoded.contains(SEP) {
     return false;
   }
@@ -137,24 +261,67 @@ fn compare_encoded_value(encoded: &str, value: &JsValue, case_sensitive: bool) -
   match (val_type, value_type) {
     ("string", JsType::String) => {
       let value_str = value.as_string().unwrap_or_default();
-      if case_sensitive {
-        val_str == value_str
-      } else {
-        val_str.eq_ignore_ascii_case(&value_str)
-      }
+      options.canonical(val_str) == options.canonical(&value_str)
     }
     ("number", JsType::Number) => {
       let value_num = value.as_f64().unwrap_or(0.0);
-      val_str.parse::<f64>().map_or(false, |v| v == value_num)
+      val_str
+        .parse::<f64>()
+        .is_ok_and(|num| numbers_equal(num, value_num, options.same_value))
+    }
+    ("bigint", JsType::BigInt) => val_str == bigint_to_string(value),
+    ("date", JsType::Date) => {
+      let value_ms = value.unchecked_ref::<js_sys::Date>().get_time();
+      val_str
+        .parse::<f64>()
+        .is_ok_and(|ms| numbers_equal(ms, value_ms, false))
     }
     ("boolean", JsType::Boolean) => {
       let value_bool = value.as_bool().unwrap_or(false);
-      val_str.parse::<bool>().map_or(false, |v| v == value_bool)
+      val_str.parse::<bool>() == Ok(value_bool)
     }
     _ => false,
   }
 }
 
+fn parse_numeric(text: &str) -> Option<f64> {
+  let is_numeric = text.chars().any(|c| c.is_ascii_digit())
+    && text
+      .chars()
+      .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'));
+  if is_numeric {
+    text.parse::<f64>().ok()
+  } else {
+    None
+  }
+}
+
+fn coerce_value(value: &JsValue) -> JsValue {
+  let Some(text) = value.as_string() else {
+    return value.clone();
+  };
+  let trimmed = text.trim();
+  match trimmed {
+    "true" => JsValue::TRUE,
+    "false" => JsValue::FALSE,
+    _ => match parse_numeric(trimmed) {
+      Some(number) => JsValue::from_f64(number),
+      None => JsValue::from_str(trimmed),
+    },
+  }
+}
+
+fn encoded_matches(encoded: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  if !options.coerce {
+    return compare_encoded_value(encoded, value, options);
+  }
+  let Some(Ok(member)) = decode_value(encoded).map(|member| encode_value(&coerce_value(&member)))
+  else {
+    return false;
+  };
+  compare_encoded_value(&member, &coerce_value(value), options)
+}
+
 fn get_string_value(value: &JsValue) -> String {
   if value.is_null() {
     return "null".to_string();
@@ -163,13 +330,40 @@ fn get_string_value(value: &JsValue) -> String {
   } else if let Some(str_val) = value.as_string() {
     return str_val;
   } else if let Some(num_val) = value.as_f64() {
-    return num_val.to_string();
+    return number_to_string(num_val);
+  } else if value.is_bigint() {
+    return bigint_to_string(value);
+  } else if let Some(date) = value.dyn_ref::<js_sys::Date>() {
+    return match date::date_millis(value) {
+      Some(_) => String::from(date.to_iso_string()),
+      None => "Invalid Date".to_string(),
+    };
   } else if let Some(bool_val) = value.as_bool() {
     return bool_val.to_string();
   }
   "unknown".to_string()
 }
 
+#[wasm_bindgen]
+pub fn lit(value: &JsValue) -> Result<String, JsValue> {
+  Ok(format!("{}{}", PREFIX_LIT, encode_value(value)?))
+}
+
+fn describe_value(value: &JsValue) -> String {
+  match value.as_string() {
+    Some(s) => format!("{:?}", s),
+    None if value.is_bigint() => format!("{}n", bigint_to_string(value)),
+    None => get_string_value(value),
+  }
+}
+
+fn encode_member(value: &JsValue) -> Result<String, JsValue> {
+  match value.as_string() {
+    Some(key) if key.starts_with(PREFIX_TEXT) => Ok(key),
+    _ => encode_value(value),
+  }
+}
+
 #[wasm_bindgen]
 pub fn not(args: &js_sys::Array) -> Result<String, JsValue> {
   let length = args.length();
@@ -180,11 +374,7 @@ pub fn not(args: &js_sys::Array) -> Result<String, JsValue> {
 
   let mut encoded_values = Vec::with_capacity(length as usize);
   for i in 0..length {
-    let value = args.get(i);
-    match encode_value(&value) {
-      Ok(encoded) => encoded_values.push(encoded),
-      Err(e) => return Err(e),
-    }
+    encoded_values.push(encode_member(&args.get(i))?);
   }
 
   Ok(format!("{}{}", PREFIX_NOT, encoded_values.join("|")))
@@ -200,11 +390,7 @@ pub fn any(args: &js_sys::Array) -> Result<String, JsValue> {
 
   let mut encoded_values = Vec::with_capacity(length as usize);
   for i in 0..length {
-    let value = args.get(i);
-    match encode_value(&value) {
-      Ok(encoded) => encoded_values.push(encoded),
-      Err(e) => return Err(e),
-    }
+    encoded_values.push(encode_member(&args.get(i))?);
   }
 
   Ok(format!("{}{}", PREFIX_ANY, encoded_values.join("|")))
@@ -222,6 +408,179 @@ pub fn regex(pattern: &str, flags: Option<String>) -> Result<String, JsValue> {
   ))
 }
 
+const TYPE_NAMES: [&str; 7] = [
+  "string",
+  "number",
+  "bigint",
+  "boolean",
+  "date",
+  "null",
+  "undefined",
+];
+
+#[wasm_bindgen(js_name = "type")]
+pub fn type_pattern(name: &str) -> Result<String, JsValue> {
+  if TYPE_NAMES.contains(&name) {
+    Ok(format!("{}{}", PREFIX_TYPE, name))
+  } else {
+    Err(JsValue::from_str(&format!(
+      "type() expects one of: {}",
+      TYPE_NAMES.join(", ")
+    )))
+  }
+}
+
+#[wasm_bindgen]
+pub fn integer() -> String {
+  format!("{}integer", PREFIX_TYPE)
+}
+
+#[wasm_bindgen]
+pub fn finite() -> String {
+  format!("{}finite", PREFIX_TYPE)
+}
+
+#[wasm_bindgen]
+pub fn nan() -> String {
+  format!("{}nan", PREFIX_TYPE)
+}
+
+fn type_matches(pattern: &str, value: &JsValue) -> bool {
+  let number = value.as_f64();
+  match &pattern[PREFIX_TYPE.len()..] {
+    "string" => value.is_string(),
+    "number" => number.is_some(),
+    "bigint" => value.is_bigint(),
+    "date" => value.is_instance_of::<js_sys::Date>(),
+    "boolean" => value.as_bool().is_some(),
+    "null" => value.is_null(),
+    "undefined" => value.is_undefined(),
+    "integer" => number.is_some_and(|n| n.is_finite() && n.fract() == 0.0),
+    "finite" => number.is_some_and(f64::is_finite),
+    "nan" => number.is_some_and(f64::is_nan),
+    _ => false,
+  }
+}
+
+fn is_numeric_value(value: &JsValue) -> bool {
+  value.is_bigint() || value.as_f64().is_some()
+}
+
+fn value_is_comparable(value: &JsValue) -> bool {
+  is_numeric_value(value) && value.as_f64().is_none_or(|n| !n.is_nan())
+}
+
+fn range_bound(bound: &JsValue, name: &str) -> Result<String, JsValue> {
+  if value_is_comparable(bound) {
+    encode_value(bound)
+  } else {
+    Err(JsValue::from_str(&format!(
+      "range() requires `{}` to be a number or bigint other than NaN",
+      name
+    )))
+  }
+}
+
+#[wasm_bindgen]
+pub fn range(min: &JsValue, max: &JsValue) -> Result<String, JsValue> {
+  let (lower, upper) = (range_bound(min, "min")?, range_bound(max, "max")?);
+  if min.gt(max) {
+    return Err(JsValue::from_str(
+      "range() requires `min` to be less than or equal to `max`",
+    ));
+  }
+  Ok(format!("{}{}|{}", PREFIX_RANGE, lower, upper))
+}
+
+fn parse_range_key(pattern: &str) -> Option<(JsValue, JsValue)> {
+  let (min, max) = pattern.strip_prefix(PREFIX_RANGE)?.split_once('|')?;
+  let bound = |encoded| decode_value(encoded).filter(value_is_comparable);
+  Some((bound(min)?, bound(max)?))
+}
+
+fn range_matches(pattern: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  let value = options.coerced(value);
+  is_numeric_value(&value)
+    && parse_range_key(pattern).is_some_and(|(min, max)| value.ge(&min) && value.le(&max))
+}
+
+fn text_pattern(op: &str, arg: &str) -> String {
+  let encoded_arg = encode_regex_key_part(arg).replace('|', "%7C");
+  format!("{}{}::{}", PREFIX_TEXT, op, encoded_arg)
+}
+
+fn length_pattern(op: &str, length: f64) -> Result<String, JsValue> {
+  if length >= 0.0 && length.fract() == 0.0 {
+    Ok(text_pattern(op, &length.to_string()))
+  } else {
+    Err(JsValue::from_str(&format!(
+      "{}() requires a non-negative integer",
+      op
+    )))
+  }
+}
+
+#[wasm_bindgen(js_name = "startsWith")]
+pub fn starts_with(prefix: &str) -> String {
+  text_pattern("startsWith", prefix)
+}
+
+#[wasm_bindgen(js_name = "endsWith")]
+pub fn ends_with(suffix: &str) -> String {
+  text_pattern("endsWith", suffix)
+}
+
+#[wasm_bindgen]
+pub fn includes(substring: &str) -> String {
+  text_pattern("includes", substring)
+}
+
+#[wasm_bindgen(js_name = "minLength")]
+pub fn min_length(length: f64) -> Result<String, JsValue> {
+  length_pattern("minLength", length)
+}
+
+#[wasm_bindgen(js_name = "maxLength")]
+pub fn max_length(length: f64) -> Result<String, JsValue> {
+  length_pattern("maxLength", length)
+}
+
+#[wasm_bindgen]
+pub fn length(length: f64) -> Result<String, JsValue> {
+  length_pattern("length", length)
+}
+
+fn parse_text_key(pattern: &str) -> Option<(&str, String)> {
+  let (op, encoded_arg) = pattern.strip_prefix(PREFIX_TEXT)?.split_once("::")?;
+  let arg = decode_regex_key_part(&encoded_arg.replace("%7C", "|"));
+  let valid = match op {
+    "startsWith" | "endsWith" | "includes" => true,
+    "minLength" | "maxLength" | "length" => arg.parse::<usize>().is_ok(),
+    _ => false,
+  };
+  valid.then_some((op, arg))
+}
+
+fn text_matches(pattern: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  let (Some(value_str), Some((op, arg))) = (value.as_string(), parse_text_key(pattern)) else {
+    return false;
+  };
+  let normalized = options.normalized(&value_str);
+  let (text, needle) = (options.case_folded(&normalized), options.canonical(&arg));
+  let length = || normalized.encode_utf16().count();
+  let bound = || arg.parse::<usize>().unwrap_or_default();
+
+  match op {
+    "startsWith" => text.starts_with(needle.as_ref()),
+    "endsWith" => text.ends_with(needle.as_ref()),
+    "includes" => text.contains(needle.as_ref()),
+    "minLength" => length() >= bound(),
+    "maxLength" => length() <= bound(),
+    "length" => length() == bound(),
+    _ => false,
+  }
+}
+
 fn encode_regex_key_part(input: &str) -> String {
   input.replace('%', "%25").replace(':', "%3A")
 }
@@ -241,7 +600,14 @@ fn parse_regex_key(pattern: &str) -> Option<(String, String)> {
   ))
 }
 
-fn wildcard_to_regex(pattern: &str, case_sensitive: bool) -> RegExp {
+fn try_compile_regex(pattern: &str, flags: &str) -> Result<RegExp, JsValue> {
+  let constructor = RegExp::new("", "").constructor();
+  let args = Array::of2(&JsValue::from_str(pattern), &JsValue::from_str(flags));
+  Reflect::construct(&constructor, &args).map(|regex| regex.unchecked_into())
+}
+
+fn wildcard_to_regex(pattern: &str, options: &MatchOptions) -> RegExp {
+  let pattern = options.canonical(pattern);
   let mut regex_str = String::with_capacity(pattern.len() * 2);
   for c in pattern.chars() {
     match c {
@@ -257,261 +623,1001 @@ fn wildcard_to_regex(pattern: &str, case_sensitive: bool) -> RegExp {
 
   regex_str = format!("^{}$", regex_str);
 
-  let flags = if !case_sensitive { "i" } else { "" };
+  RegExp::new(&regex_str, "")
+}
 
-  RegExp::new(&regex_str, flags)
+fn wildcard_test(regex: &RegExp, value: &JsValue, options: &MatchOptions) -> bool {
+  value
+    .as_string()
+    .is_some_and(|text| regex.test(&options.canonical(&text)))
 }
 
-fn try_composite_pattern(
-  value: &JsValue,
-  entries: &[(String, JsValue)],
-  prefix: &str,
+fn regex_test(regex: &RegExp, text: &str, options: &MatchOptions) -> bool {
+  regex.set_last_index(0);
+  if regex.test(text) {
+    return true;
+  }
+  // The `i` flag only folds case character by character, so also try the
+  // fully folded text, which lets `/strasse/i` match "STRAßE".
+  let folded = options.case_folded(text);
+  folded != text && {
+    regex.set_last_index(0);
+    regex.test(&folded)
+  }
+}
+
+fn member_matches(member: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  if member.starts_with(PREFIX_TEXT) {
+    text_matches(member, value, options)
+  } else {
+    encoded_matches(member, value, options)
+  }
+}
+
+fn composite_matches(pattern: &str, prefix: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  pattern[prefix.len()..]
+    .split('|')
+    .any(|member| member_matches(member, value, options))
+}
+
+fn effective_regex_flags(flags: String, case_sensitive: bool) -> String {
+  if !case_sensitive && !flags.contains('i') {
+    format!("{}i", flags)
+  } else {
+    flags
+  }
+}
+
+fn regex_matches(pattern: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  if let Some((regex_pattern, flags)) = parse_regex_key(pattern) {
+    let effective_flags = effective_regex_flags(flags, options.case_sensitive);
+    let regex = RegExp::new(&regex_pattern, &effective_flags);
+    regex_test(&regex, &get_string_value(value), options)
+  } else {
+    false
+  }
+}
+
+fn wildcard_matches(pattern: &str, value: &JsValue, options: &MatchOptions) -> bool {
+  wildcard_test(&wildcard_to_regex(pattern, options), value, options)
+}
+
+// Upper- then lower-casing each character folds "ß" and "SS" alike.
+fn fold_case(text: &str, turkic: bool) -> String {
+  let mut folded = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      'I' | 'ı' if turkic => folded.push('ı'),
+      'İ' | 'i' if turkic => folded.push('i'),
+      'ı' => folded.push('ı'),
+      _ => folded.extend(c.to_uppercase().flat_map(char::to_lowercase)),
+    }
+  }
+  folded
+}
+
+#[derive(Clone, Copy, Default)]
+struct Normalize {
+  trim: bool,
+  form: Option<&'static str>,
+  collapse_whitespace: bool,
+  strip_diacritics: bool,
+}
+
+impl Normalize {
+  fn from_js(value: &JsValue) -> Result<Self, JsValue> {
+    if !value.is_object() {
+      return Ok(Self::default());
+    }
+    let get = |name: &str| Reflect::get(value, &JsValue::from_str(name)).unwrap_or_default();
+    let form = get("form");
+    let known = ["NFC", "NFD", "NFKC", "NFKD"]
+      .into_iter()
+      .find(|known| form.as_string().as_deref() == Some(*known));
+    if known.is_none() && !form.is_undefined() {
+      return Err(JsValue::from_str(
+        "normalize.form must be one of 'NFC', 'NFD', 'NFKC' or 'NFKD'",
+      ));
+    }
+    Ok(Self {
+      trim: get("trim").is_truthy(),
+      form: known,
+      collapse_whitespace: get("collapseWhitespace").is_truthy(),
+      strip_diacritics: get("stripDiacritics").is_truthy(),
+    })
+  }
+
+  fn enabled(&self) -> bool {
+    self.trim || self.form.is_some() || self.collapse_whitespace || self.strip_diacritics
+  }
+
+  fn apply(&self, text: &str) -> String {
+    let mut text = text.to_string();
+    if self.strip_diacritics {
+      text = String::from(JsString::from(text.as_str()).normalize("NFD"))
+        .chars()
+        .filter(|c| !is_combining_mark(*c))
+        .collect();
+    }
+    if let Some(form) = self.form {
+      text = JsString::from(text.as_str()).normalize(form).into();
+    }
+    if self.collapse_whitespace {
+      text = collapse_whitespace(&text);
+    }
+    if self.trim {
+      text = text.trim().to_string();
+    }
+    text
+  }
+}
+
+fn is_combining_mark(c: char) -> bool {
+  matches!(
+    c,
+    '\u{0300}'..='\u{036F}'
+      | '\u{1AB0}'..='\u{1AFF}'
+      | '\u{1DC0}'..='\u{1DFF}'
+      | '\u{20D0}'..='\u{20FF}'
+      | '\u{FE20}'..='\u{FE2F}'
+  )
+}
+
+fn collapse_whitespace(text: &str) -> String {
+  let mut collapsed = String::with_capacity(text.len());
+  let mut in_space = false;
+  for c in text.chars() {
+    if c.is_whitespace() {
+      if !in_space {
+        collapsed.push(' ');
+      }
+      in_space = true;
+    } else {
+      collapsed.push(c);
+      in_space = false;
+    }
+  }
+  collapsed
+}
+
+#[derive(Clone)]
+struct MatchOptions {
   case_sensitive: bool,
-  match_fn: impl Fn(bool) -> bool,
-) -> Option<JsValue> {
-  for (pattern, handler) in entries {
-    let values_part = &pattern[prefix.len()..];
-    let values: Vec<&str> = values_part.split('|').collect();
-
-    let mut matched = false;
-    for val_str in values {
-      if compare_encoded_value(val_str, value, case_sensitive) {
-        matched = true;
-        break;
+  constants: bool,
+  strict: bool,
+  coerce: bool,
+  same_value: bool,
+  turkic: bool,
+  normalize: Normalize,
+  context: JsValue,
+}
+impl MatchOptions {
+  fn from_js(options: Option<&Object>) -> Result<Self, JsValue> {
+    let flag = |name: &str, default: bool| match options {
+      Some(opts) => match Reflect::get(opts, &JsValue::from_str(name)) {
+        Ok(val) if !val.is_undefined() => !val.is_falsy(),
+        _ => default,
+      },
+      None => default,
+    };
+
+    let context = options
+      .and_then(|opts| Reflect::get(opts, &JsValue::from_str("context")).ok())
+      .unwrap_or(JsValue::UNDEFINED);
+
+    let equality = options
+      .and_then(|opts| Reflect::get(opts, &JsValue::from_str("equality")).ok())
+      .unwrap_or(JsValue::UNDEFINED);
+    let same_value = match equality.as_string().as_deref() {
+      _ if equality.is_undefined() => false,
+      Some("sameValueZero") => false,
+      Some("sameValue") => true,
+      _ => {
+        return Err(JsValue::from_str(
+          "equality must be either 'sameValueZero' or 'sameValue'",
+        ))
       }
+    };
+
+    let locale = options
+      .and_then(|opts| Reflect::get(opts, &JsValue::from_str("locale")).ok())
+      .and_then(|locale| locale.as_string());
+    let turkic = locale.is_some_and(|locale| {
+      let language = locale.split(['-', '_']).next().unwrap_or_default();
+      language.eq_ignore_ascii_case("tr") || language.eq_ignore_ascii_case("az")
+    });
+
+    let normalize =
+      match options.and_then(|opts| Reflect::get(opts, &JsValue::from_str("normalize")).ok()) {
+        Some(normalize) => Normalize::from_js(&normalize)?,
+        None => Normalize::default(),
+      };
+
+    // An options object without `caseSensitive` matches case-insensitively, as it always has.
+    let case_sensitive = options.is_none_or(|opts| {
+      Reflect::get(opts, &JsValue::from_str("caseSensitive")).is_ok_and(|val| val.is_truthy())
+    });
+
+    Ok(Self {
+      case_sensitive,
+      constants: flag("constants", false),
+      strict: flag("strict", false),
+      coerce: flag("coerce", false),
+      same_value,
+      turkic,
+      normalize,
+      context,
+    })
+  }
+
+  fn accepts(&self, handler: &JsValue) -> bool {
+    self.constants || handler.is_function()
+  }
+
+  fn canonicalizes(&self) -> bool {
+    !self.case_sensitive || self.normalize.enabled() || self.coerce
+  }
+
+  fn normalized<'a>(&self, text: &'a str) -> Cow<'a, str> {
+    if self.normalize.enabled() {
+      Cow::Owned(self.normalize.apply(text))
+    } else {
+      Cow::Borrowed(text)
+    }
+  }
+
+  fn case_folded<'a>(&self, text: &'a str) -> Cow<'a, str> {
+    if self.case_sensitive {
+      Cow::Borrowed(text)
+    } else {
+      Cow::Owned(fold_case(text, self.turkic))
     }
+  }
 
-    if match_fn(matched) {
-      if let Some(func) = handler.dyn_ref::<Function>() {
-        return func.call0(&JsValue::NULL).ok();
+  fn canonical<'a>(&self, text: &'a str) -> Cow<'a, str> {
+    match self.normalized(text) {
+      Cow::Borrowed(text) => self.case_folded(text),
+      Cow::Owned(text) => Cow::Owned(self.case_folded(&text).into_owned()),
+    }
+  }
+
+  fn coerced_key<'a>(&self, key: &'a str) -> Cow<'a, str> {
+    if self.coerce {
+      Cow::Owned(get_string_value(&coerce_value(&JsValue::from_str(key))))
+    } else {
+      Cow::Borrowed(key)
+    }
+  }
+
+  fn canonical_key<'a>(&self, key: &'a str) -> Cow<'a, str> {
+    let Some(encoded) = key.strip_prefix(PREFIX_LIT) else {
+      return match self.coerced_key(key) {
+        Cow::Borrowed(key) => self.canonical(key),
+        Cow::Owned(key) => Cow::Owned(self.canonical(&key).into_owned()),
+      };
+    };
+    let encoded = match decode_value(encoded).filter(|_| self.coerce) {
+      Some(value) => encode_value(&coerce_value(&value)).map_or(Cow::Borrowed(encoded), Cow::Owned),
+      None => Cow::Borrowed(encoded),
+    };
+    match (encoded.split_once(SEP), &encoded) {
+      (Some(("string", text)), _) => Cow::Owned(format!(
+        "{}string{}{}",
+        PREFIX_LIT,
+        SEP,
+        self.canonical(text)
+      )),
+      (_, Cow::Owned(encoded)) => Cow::Owned(format!("{}{}", PREFIX_LIT, encoded)),
+      (_, Cow::Borrowed(_)) => Cow::Borrowed(key),
+    }
+  }
+
+  fn coerced(&self, value: &JsValue) -> JsValue {
+    if self.coerce {
+      coerce_value(value)
+    } else {
+      value.clone()
+    }
+  }
+}
+
+fn get_handler(patterns: &Object, key: &str, options: &MatchOptions) -> Option<JsValue> {
+  let key = JsValue::from_str(key);
+  if !patterns.has_own_property(&key) {
+    return None;
+  }
+  Reflect::get(patterns, &key)
+    .ok()
+    .filter(|handler| options.accepts(handler))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ArmKind {
+  Some,
+  None,
+  Exact,
+  Literal,
+  When,
+  Any,
+  Not,
+  Range,
+  Date,
+  Cron,
+  Text,
+  Regex,
+  Wildcard,
+  Fuzzy,
+  Phonetic,
+  Type,
+  Default,
+}
+
+impl ArmKind {
+  fn of(key: &str) -> Self {
+    if key == SOME_VALUE {
+      ArmKind::Some
+    } else if key == NONE_VALUE {
+      ArmKind::None
+    } else if key == DEFAULT_HANDLER {
+      ArmKind::Default
+    } else if key.starts_with(PREFIX_LIT) {
+      ArmKind::Literal
+    } else if key.starts_with(PREFIX_WHEN) {
+      ArmKind::When
+    } else if key.starts_with(PREFIX_ANY) {
+      ArmKind::Any
+    } else if key.starts_with(PREFIX_NOT) {
+      ArmKind::Not
+    } else if key.starts_with(PREFIX_RANGE) {
+      ArmKind::Range
+    } else if key.starts_with(PREFIX_DATE) {
+      ArmKind::Date
+    } else if key.starts_with(PREFIX_CRON) {
+      ArmKind::Cron
+    } else if key.starts_with(PREFIX_TEXT) {
+      ArmKind::Text
+    } else if key.starts_with(PREFIX_REGEX) {
+      ArmKind::Regex
+    } else if key.starts_with(PREFIX_FUZZY) {
+      ArmKind::Fuzzy
+    } else if key.starts_with(PREFIX_PHONETIC) {
+      ArmKind::Phonetic
+    } else if key.starts_with(PREFIX_TYPE) {
+      ArmKind::Type
+    } else if key.contains('*') || key.contains('?') {
+      ArmKind::Wildcard
+    } else {
+      ArmKind::Exact
+    }
+  }
+
+  fn as_str(self) -> &'static str {
+    match self {
+      ArmKind::Some => "some",
+      ArmKind::None => "none",
+      ArmKind::Exact | ArmKind::Literal => "exact",
+      ArmKind::When => "when",
+      ArmKind::Any => "any",
+      ArmKind::Not => "not",
+      ArmKind::Range => "range",
+      ArmKind::Date => "date",
+      ArmKind::Cron => "cron",
+      ArmKind::Text => "text",
+      ArmKind::Regex => "regex",
+      ArmKind::Wildcard => "wildcard",
+      ArmKind::Fuzzy => "fuzzy",
+      ArmKind::Phonetic => "phonetic",
+      ArmKind::Type => "type",
+      ArmKind::Default => "default",
+    }
+  }
+
+  fn matches(
+    self,
+    key: &str,
+    value: &JsValue,
+    options: &MatchOptions,
+    run_predicates: bool,
+  ) -> bool {
+    match self {
+      ArmKind::Some => !value.is_null() && !value.is_undefined(),
+      ArmKind::None => value.is_null() || value.is_undefined(),
+      ArmKind::Exact => {
+        let value = options.coerced(value);
+        (!options.strict || value.is_string())
+          && options.canonical(&get_string_value(&value))
+            == options.canonical(&options.coerced_key(key))
       }
+      ArmKind::Literal => encoded_matches(&key[PREFIX_LIT.len()..], value, options),
+      ArmKind::When => {
+        let function_hash = &key[PREFIX_WHEN.len()..];
+        if function_hash == "true" {
+          true
+        } else if run_predicates {
+          match get_predicate_function(function_hash) {
+            Ok(Some(predicate)) => predicate
+              .call2(&JsValue::NULL, value, &options.context)
+              .is_ok_and(|result| result.as_bool() == Some(true)),
+            _ => false,
+          }
+        } else {
+          false
+        }
+      }
+      ArmKind::Any => composite_matches(key, PREFIX_ANY, value, options),
+      ArmKind::Not => !composite_matches(key, PREFIX_NOT, value, options),
+      ArmKind::Range => range_matches(key, value, options),
+      ArmKind::Date => date::date_matches(key, value),
+      ArmKind::Cron => cron::cron_matches(key, value),
+      ArmKind::Text => text_matches(key, value, options),
+      ArmKind::Regex => regex_matches(key, value, options),
+      ArmKind::Wildcard => wildcard_matches(key, value, options),
+      ArmKind::Fuzzy => fuzzy::fuzzy_matches(key, value, options),
+      ArmKind::Phonetic => phonetic::phonetic_matches(key, value),
+      ArmKind::Type => type_matches(key, value),
+      ArmKind::Default => true,
     }
   }
+}
 
-  None
+const GROUP_ORDER: [ArmKind; 12] = [
+  ArmKind::When,
+  ArmKind::Any,
+  ArmKind::Not,
+  ArmKind::Range,
+  ArmKind::Date,
+  ArmKind::Cron,
+  ArmKind::Text,
+  ArmKind::Regex,
+  ArmKind::Wildcard,
+  ArmKind::Fuzzy,
+  ArmKind::Phonetic,
+  ArmKind::Type,
+];
+
+fn wildcard_count(pattern: &str) -> usize {
+  pattern.chars().filter(|&c| c == '*' || c == '?').count()
 }
 
+#[derive(Default)]
 struct PatternGroups {
   when: Vec<(String, JsValue)>,
   any: Vec<(String, JsValue)>,
   not: Vec<(String, JsValue)>,
+  range: Vec<(String, JsValue)>,
+  dates: Vec<(String, JsValue)>,
+  cron: Vec<(String, JsValue)>,
+  text: Vec<(String, JsValue)>,
   regex: Vec<(String, JsValue)>,
   wildcard: Vec<(String, JsValue)>,
+  fuzzy: Vec<(String, JsValue)>,
+  phonetic: Vec<(String, JsValue)>,
+  types: Vec<(String, JsValue)>,
+  // Exact and `lit` keys by canonical form, kept when case or text is normalized.
+  folded: HashMap<String, Vec<String>>,
 }
 impl PatternGroups {
-  fn from_object(obj: &Object) -> Self {
+  fn from_object(obj: &Object, options: &MatchOptions) -> Self {
     let keys = Object::keys(obj);
     let length = keys.length();
 
     let mut when = Vec::with_capacity(length as usize);
     let mut any = Vec::with_capacity(length as usize);
     let mut not = Vec::with_capacity(length as usize);
+    let mut range = Vec::with_capacity(length as usize);
+    let mut dates = Vec::with_capacity(length as usize);
+    let mut cron = Vec::with_capacity(length as usize);
+    let mut text = Vec::with_capacity(length as usize);
     let mut regex = Vec::with_capacity(length as usize);
     let mut wildcard = Vec::with_capacity(length as usize);
+    let mut fuzzy = Vec::with_capacity(length as usize);
+    let mut phonetic = Vec::with_capacity(length as usize);
+    let mut types = Vec::with_capacity(length as usize);
+    let mut folded: HashMap<String, Vec<String>> = HashMap::new();
 
     for i in 0..length {
       let key = keys.get(i);
       if let Some(key_str) = key.as_string() {
-        if key_str == SOME_VALUE || key_str == NONE_VALUE || key_str == DEFAULT_HANDLER {
-          continue;
-        }
-        if let Ok(value) = Reflect::get(obj, &key) {
-          if key_str.starts_with(PREFIX_WHEN) {
-            when.push((key_str, value));
-          } else if key_str.starts_with(PREFIX_ANY) {
-            any.push((key_str, value));
-          } else if key_str.starts_with(PREFIX_NOT) {
-            not.push((key_str, value));
-          } else if key_str.starts_with(PREFIX_REGEX) {
-            regex.push((key_str, value));
-          } else if key_str.contains('*') || key_str.contains('?') {
-            wildcard.push((key_str, value));
+        let group = match ArmKind::of(&key_str) {
+          ArmKind::Exact | ArmKind::Literal if options.canonicalizes() => {
+            folded
+              .entry(options.canonical_key(&key_str).into_owned())
+              .or_default()
+              .push(key_str);
+            continue;
           }
+          ArmKind::When => &mut when,
+          ArmKind::Any => &mut any,
+          ArmKind::Not => &mut not,
+          ArmKind::Range => &mut range,
+          ArmKind::Date => &mut dates,
+          ArmKind::Cron => &mut cron,
+          ArmKind::Text => &mut text,
+          ArmKind::Regex => &mut regex,
+          ArmKind::Wildcard => &mut wildcard,
+          ArmKind::Fuzzy => &mut fuzzy,
+          ArmKind::Phonetic => &mut phonetic,
+          ArmKind::Type => &mut types,
+          _ => continue,
+        };
+        if let Ok(value) = Reflect::get(obj, &key) {
+          group.push((key_str, value));
         }
       }
     }
 
-    wildcard.sort_by(|(pat_a, _), (pat_b, _)| {
-      let wildcard_count_a = pat_a.chars().filter(|&c| c == '*' || c == '?').count();
-      let wildcard_count_b = pat_b.chars().filter(|&c| c == '*' || c == '?').count();
-      wildcard_count_a.cmp(&wildcard_count_b)
-    });
+    wildcard.sort_by_key(|(pattern, _)| wildcard_count(pattern));
 
     Self {
       when,
       any,
       not,
+      range,
+      dates,
+      cron,
+      text,
       regex,
       wildcard,
+      fuzzy,
+      phonetic,
+      types,
+      folded,
+    }
+  }
+
+  fn group(&self, kind: ArmKind) -> &[(String, JsValue)] {
+    match kind {
+      ArmKind::When => &self.when,
+      ArmKind::Any => &self.any,
+      ArmKind::Not => &self.not,
+      ArmKind::Range => &self.range,
+      ArmKind::Date => &self.dates,
+      ArmKind::Cron => &self.cron,
+      ArmKind::Text => &self.text,
+      ArmKind::Regex => &self.regex,
+      ArmKind::Wildcard => &self.wildcard,
+      ArmKind::Fuzzy => &self.fuzzy,
+      ArmKind::Phonetic => &self.phonetic,
+      ArmKind::Type => &self.types,
+      _ => &[],
+    }
+  }
+
+  fn group_mut(&mut self, kind: ArmKind) -> Option<&mut Vec<(String, JsValue)>> {
+    match kind {
+      ArmKind::When => Some(&mut self.when),
+      ArmKind::Any => Some(&mut self.any),
+      ArmKind::Not => Some(&mut self.not),
+      ArmKind::Range => Some(&mut self.range),
+      ArmKind::Date => Some(&mut self.dates),
+      ArmKind::Cron => Some(&mut self.cron),
+      ArmKind::Text => Some(&mut self.text),
+      ArmKind::Regex => Some(&mut self.regex),
+      ArmKind::Wildcard => Some(&mut self.wildcard),
+      ArmKind::Fuzzy => Some(&mut self.fuzzy),
+      ArmKind::Phonetic => Some(&mut self.phonetic),
+      ArmKind::Type => Some(&mut self.types),
+      _ => None,
+    }
+  }
+
+  fn folded_keys(&self, folded: &str) -> &[String] {
+    self.folded.get(folded).map_or(&[], Vec::as_slice)
+  }
+
+  fn collision(&self) -> Option<&[String]> {
+    self
+      .folded
+      .iter()
+      .filter(|(_, keys)| keys.len() > 1)
+      .min_by_key(|(folded, _)| folded.as_str())
+      .map(|(_, keys)| keys.as_slice())
+  }
+
+  fn insert(&mut self, key: String, value: JsValue, options: &MatchOptions) {
+    let kind = ArmKind::of(&key);
+    if matches!(kind, ArmKind::Exact | ArmKind::Literal) && options.canonicalizes() {
+      self
+        .folded
+        .entry(options.canonical_key(&key).into_owned())
+        .or_default()
+        .push(key);
+      return;
+    }
+    let Some(group) = self.group_mut(kind) else {
+      return;
+    };
+    let index = if kind == ArmKind::Wildcard {
+      let count = wildcard_count(&key);
+      group.partition_point(|(pattern, _)| wildcard_count(pattern) <= count)
+    } else {
+      group.len()
+    };
+    group.insert(index, (key, value));
+  }
+
+  fn remove(&mut self, key: &str, options: &MatchOptions) {
+    let kind = ArmKind::of(key);
+    if matches!(kind, ArmKind::Exact | ArmKind::Literal) && options.canonicalizes() {
+      let folded = options.canonical_key(key);
+      if let Some(keys) = self.folded.get_mut(folded.as_ref()) {
+        keys.retain(|pattern| pattern != key);
+        if keys.is_empty() {
+          self.folded.remove(folded.as_ref());
+        }
+      }
+    } else if let Some(group) = self.group_mut(kind) {
+      group.retain(|(pattern, _)| pattern != key);
     }
   }
 }
-#[wasm_bindgen(js_name = "match")]
-pub fn match_pattern(
+
+struct MatchedArm {
+  kind: ArmKind,
+  key: String,
+  handler: JsValue,
+}
+
+fn call_handler(
+  handler: &JsValue,
   value: &JsValue,
-  patterns: &Object,
-  options: Option<Object>,
+  options: &MatchOptions,
 ) -> Result<JsValue, JsValue> {
-  let case_sensitive = if let Some(opts) = options {
-    match Reflect::get(&opts, &JsValue::from_str("caseSensitive")) {
-      Ok(val) => !val.is_falsy(),
-      _ => true,
+  match handler.dyn_ref::<Function>() {
+    Some(func) => func.call2(&JsValue::NULL, value, &options.context),
+    None => Ok(handler.clone()),
+  }
+}
+
+impl MatchedArm {
+  fn call(&self, value: &JsValue, options: &MatchOptions) -> Result<JsValue, JsValue> {
+    match (
+      self.distance(value, options),
+      self.handler.dyn_ref::<Function>(),
+    ) {
+      (Some(distance), Some(func)) => func.call3(
+        &JsValue::NULL,
+        value,
+        &options.context,
+        &JsValue::from(distance as u32),
+      ),
+      _ => call_handler(&self.handler, value, options),
     }
-  } else {
-    true
+  }
+
+  fn distance(&self, value: &JsValue, options: &MatchOptions) -> Option<usize> {
+    match self.kind {
+      ArmKind::Fuzzy => fuzzy::fuzzy_distance(&self.key, value, options),
+      _ => None,
+    }
+  }
+
+  fn to_js(&self) -> Object {
+    let obj = Object::new();
+    let _ = Reflect::set(
+      &obj,
+      &JsValue::from_str("key"),
+      &JsValue::from_str(&self.key),
+    );
+    let _ = Reflect::set(
+      &obj,
+      &JsValue::from_str("kind"),
+      &JsValue::from_str(self.kind.as_str()),
+    );
+    obj
+  }
+}
+
+fn literal_keys(value: &JsValue, options: &MatchOptions, keys: &mut Vec<(ArmKind, String)>) {
+  let literals = match value.as_f64() {
+    Some(number) if number == 0.0 && !options.same_value => ["0", "-0"]
+      .iter()
+      .map(|zero| format!("{}number{}{}", PREFIX_LIT, SEP, zero))
+      .collect(),
+    _ => encode_value(value)
+      .map(|encoded| format!("{}{}", PREFIX_LIT, encoded))
+      .into_iter()
+      .collect::<Vec<_>>(),
   };
+  for literal in literals {
+    if !keys.iter().any(|(_, key)| *key == literal) {
+      keys.push((ArmKind::Literal, literal));
+    }
+  }
+}
 
-  if !value.is_null() && !value.is_undefined() {
-    if let Ok(some_handler) = Reflect::get(patterns, &JsValue::from_str(SOME_VALUE)) {
-      if let Some(func) = some_handler.dyn_ref::<Function>() {
-        return func.call0(&JsValue::NULL);
-      }
+fn exact_keys(value: &JsValue, options: &MatchOptions) -> Vec<(ArmKind, String)> {
+  let coerced = &options.coerced(value);
+  let mut keys = Vec::with_capacity(4);
+  literal_keys(coerced, options, &mut keys);
+  if options.coerce {
+    literal_keys(value, options, &mut keys);
+  }
+  if !options.strict || coerced.is_string() {
+    keys.push((ArmKind::Exact, get_string_value(coerced)));
+  }
+  keys
+}
+
+fn exact_arm<'g>(
+  patterns: &Object,
+  groups: impl FnOnce() -> &'g PatternGroups,
+  kind: ArmKind,
+  key: String,
+  options: &MatchOptions,
+) -> Option<MatchedArm> {
+  if let Some(handler) = get_handler(patterns, &key, options) {
+    return Some(MatchedArm { kind, key, handler });
+  }
+  if !options.canonicalizes() {
+    return None;
+  }
+  groups()
+    .folded_keys(&options.canonical_key(&key))
+    .iter()
+    .find_map(|candidate| {
+      get_handler(patterns, candidate, options).map(|handler| MatchedArm {
+        kind,
+        key: candidate.clone(),
+        handler,
+      })
+    })
+}
+
+fn collision_error(keys: &[String]) -> JsValue {
+  JsValue::from_str(&format!(
+    "Pattern keys {} only differ by case, normalization or coercion and collide when matching",
+    keys
+      .iter()
+      .map(|key| format!("`{}`", key))
+      .collect::<Vec<_>>()
+      .join(", ")
+  ))
+}
+
+struct PatternTable<'a> {
+  patterns: &'a Object,
+  options: &'a MatchOptions,
+  groups: OnceCell<PatternGroups>,
+  prebuilt: Option<&'a PatternGroups>,
+}
+
+impl<'a> PatternTable<'a> {
+  fn new(patterns: &'a Object, options: &'a MatchOptions) -> Self {
+    Self {
+      patterns,
+      options,
+      groups: OnceCell::new(),
+      prebuilt: None,
     }
-  } else if let Ok(none_handler) = Reflect::get(patterns, &JsValue::from_str(NONE_VALUE)) {
-    if let Some(func) = none_handler.dyn_ref::<Function>() {
-      return func.call0(&JsValue::NULL);
+  }
+
+  fn with_groups(
+    patterns: &'a Object,
+    options: &'a MatchOptions,
+    groups: &'a PatternGroups,
+  ) -> Self {
+    Self {
+      prebuilt: Some(groups),
+      ..Self::new(patterns, options)
     }
   }
 
-  let string_value = get_string_value(value);
+  fn check_collisions(&self) -> Result<(), JsValue> {
+    if !self.options.canonicalizes() {
+      return Ok(());
+    }
+    match self.groups().collision() {
+      Some(keys) => Err(collision_error(keys)),
+      None => Ok(()),
+    }
+  }
 
-  if let Ok(handler) = Reflect::get(patterns, &JsValue::from_str(&string_value)) {
-    if let Some(func) = handler.dyn_ref::<Function>() {
-      return func.call0(&JsValue::NULL);
+  fn groups(&self) -> &PatternGroups {
+    match self.prebuilt {
+      Some(groups) => groups,
+      None => self
+        .groups
+        .get_or_init(|| PatternGroups::from_object(self.patterns, self.options)),
     }
   }
 
-  let pattern_groups = PatternGroups::from_object(patterns);
+  fn handler(&self, key: &str) -> Option<JsValue> {
+    get_handler(self.patterns, key, self.options)
+  }
 
-  for (pattern, handler) in &pattern_groups.when {
-    if pattern == &format!("{}{}", PREFIX_WHEN, true) {
-      if let Some(func) = handler.dyn_ref::<Function>() {
-        return func.call0(&JsValue::NULL);
+  fn visit_head(
+    &self,
+    value: &JsValue,
+    visit: &mut dyn FnMut(MatchedArm) -> ControlFlow<()>,
+  ) -> ControlFlow<()> {
+    let (kind, key) = if !value.is_null() && !value.is_undefined() {
+      (ArmKind::Some, SOME_VALUE)
+    } else {
+      (ArmKind::None, NONE_VALUE)
+    };
+    if let Some(handler) = self.handler(key) {
+      visit(MatchedArm {
+        kind,
+        key: key.to_string(),
+        handler,
+      })?;
+    }
+
+    for (kind, key) in exact_keys(value, self.options) {
+      if let Some(arm) = exact_arm(self.patterns, || self.groups(), kind, key, self.options) {
+        visit(arm)?;
       }
     }
-    let function_hash = &pattern[PREFIX_WHEN.len()..];
-    if let Ok(Some(predicate)) = get_predicate_function(function_hash) {
-      if let Ok(result) = predicate.call1(&JsValue::NULL, value) {
-        if result.as_bool() == Some(true) {
-          if let Some(func) = handler.dyn_ref::<Function>() {
-            return func.call0(&JsValue::NULL);
-          }
-        }
+
+    ControlFlow::Continue(())
+  }
+
+  fn visit_group(
+    &self,
+    kind: ArmKind,
+    value: &JsValue,
+    run_predicates: bool,
+    visit: &mut dyn FnMut(MatchedArm) -> ControlFlow<()>,
+  ) -> ControlFlow<()> {
+    for (pattern, handler) in self.groups().group(kind) {
+      if self.options.accepts(handler) && kind.matches(pattern, value, self.options, run_predicates)
+      {
+        visit(MatchedArm {
+          kind,
+          key: pattern.clone(),
+          handler: handler.clone(),
+        })?;
       }
     }
+
+    ControlFlow::Continue(())
   }
 
-  if let Some(result) = try_composite_pattern(
-    value,
-    &pattern_groups.any,
-    PREFIX_ANY,
-    case_sensitive,
-    |matched| matched,
-  ) {
-    return Ok(result);
+  fn visit(
+    &self,
+    value: &JsValue,
+    run_predicates: bool,
+    visit: &mut dyn FnMut(MatchedArm) -> ControlFlow<()>,
+  ) -> ControlFlow<()> {
+    self.visit_head(value, visit)?;
+    for kind in GROUP_ORDER {
+      self.visit_group(kind, value, run_predicates, visit)?;
+    }
+
+    ControlFlow::Continue(())
   }
 
-  if let Some(result) = try_composite_pattern(
-    value,
-    &pattern_groups.not,
-    PREFIX_NOT,
-    case_sensitive,
-    |matched| !matched,
-  ) {
-    return Ok(result);
+  fn first(
+    walk: impl FnOnce(&mut dyn FnMut(MatchedArm) -> ControlFlow<()>) -> ControlFlow<()>,
+  ) -> Option<MatchedArm> {
+    let mut found = None;
+    let _ = walk(&mut |arm| {
+      found = Some(arm);
+      ControlFlow::Break(())
+    });
+    found
   }
 
-  for (pattern, handler) in &pattern_groups.regex {
-    if let Some((regex_pattern, flags)) = parse_regex_key(pattern) {
-      let effective_flags = if !case_sensitive && !flags.contains('i') {
-        format!("{}i", flags)
-      } else {
-        flags
-      };
+  fn default_arm(&self) -> Option<MatchedArm> {
+    self.handler(DEFAULT_HANDLER).map(|handler| MatchedArm {
+      kind: ArmKind::Default,
+      key: DEFAULT_HANDLER.to_string(),
+      handler,
+    })
+  }
 
-      let regex = RegExp::new(&regex_pattern, &effective_flags);
+  fn find(&self, value: &JsValue, run_predicates: bool) -> Option<MatchedArm> {
+    Self::first(|visit| self.visit(value, run_predicates, visit)).or_else(|| self.default_arm())
+  }
 
-      if regex.test(&string_value) {
-        if let Some(func) = handler.dyn_ref::<Function>() {
-          return func.call0(&JsValue::NULL);
-        }
-      }
-    }
+  fn no_match_error(&self, value: &JsValue) -> JsValue {
+    let keys = Object::keys(self.patterns);
+    let attempted_patterns: Vec<String> = keys
+      .to_vec()
+      .into_iter()
+      .filter_map(|v| v.as_string())
+      .collect();
+
+    let error_msg = format!(
+      "No pattern matched for: {}. Attempted patterns: {}",
+      get_string_value(value),
+      attempted_patterns.join(", ")
+    );
+
+    JsValue::from_str(&error_msg)
   }
+}
 
-  if value.is_string() && !pattern_groups.wildcard.is_empty() {
-    if let Some(value_str) = value.as_string() {
-      for (pattern, handler) in &pattern_groups.wildcard {
-        let regex = wildcard_to_regex(pattern, case_sensitive);
+#[wasm_bindgen(js_name = "match")]
+pub fn match_pattern(
+  value: &JsValue,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<JsValue, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(patterns, &options);
+  table.check_collisions()?;
 
-        if regex.test(&value_str) {
-          if let Some(func) = handler.dyn_ref::<Function>() {
-            return func.call0(&JsValue::NULL);
-          }
-        }
-      }
-    }
+  match table.find(value, true) {
+    Some(arm) => arm.call(value, &options),
+    None => Err(table.no_match_error(value)),
   }
+}
 
-  let default_handler = Reflect::get(patterns, &JsValue::from_str(DEFAULT_HANDLER)).ok();
-  if let Some(default_handler) = default_handler {
-    if let Some(func) = default_handler.dyn_ref::<Function>() {
-      return func.call0(&JsValue::NULL);
+#[wasm_bindgen(js_name = "matchAll")]
+pub fn match_all(
+  value: &JsValue,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<Array, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(patterns, &options);
+  table.check_collisions()?;
+
+  let mut arms: Vec<MatchedArm> = Vec::new();
+  let _ = table.visit(value, true, &mut |arm| {
+    if !arms.iter().any(|seen| seen.key == arm.key) {
+      arms.push(arm);
     }
+    ControlFlow::Continue(())
+  });
+  if arms.is_empty() {
+    arms.extend(table.default_arm());
   }
 
-  let keys = Object::keys(patterns);
-  let attempted_patterns: Vec<String> = keys
-    .to_vec()
-    .into_iter()
-    .filter_map(|v| v.as_string())
-    .collect();
+  let results = Array::new();
+  for arm in &arms {
+    results.push(&arm.call(value, &options)?);
+  }
+  Ok(results)
+}
 
-  let error_msg = format!(
-    "No pattern matched for: {}. Attempted patterns: {}",
-    string_value,
-    attempted_patterns.join(", ")
-  );
+#[wasm_bindgen]
+pub fn which(
+  value: &JsValue,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<JsValue, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = PatternTable::new(patterns, &options);
+  table.check_collisions()?;
+
+  Ok(
+    match PatternTable::first(|visit| table.visit(value, true, visit)) {
+      Some(arm) => arm.to_js().into(),
+      None => JsValue::UNDEFINED,
+    },
+  )
+}
 
-  Err(JsValue::from_str(&error_msg))
+fn matches_key(value: &JsValue, key: &str, options: &MatchOptions) -> bool {
+  if key == DEFAULT_HANDLER {
+    return false;
+  }
+  ArmKind::Exact.matches(key, value, options, true)
+    || ArmKind::of(key).matches(key, value, options, true)
 }
 
 #[wasm_bindgen(js_name = "ifLet")]
 pub fn if_let(value: &JsValue, pattern: &JsValue, handler: &Function) -> JsValue {
-  let pattern_str = get_string_value(pattern);
-
-  let patterns = Object::new();
-  let _ = Reflect::set(&patterns, &JsValue::from_str(&pattern_str), handler);
-  let _ = Reflect::set(
-    &patterns,
-    &JsValue::from_str(DEFAULT_HANDLER),
-    &Function::new_no_args("return undefined;"),
-  );
+  let Ok(options) = MatchOptions::from_js(None) else {
+    return JsValue::UNDEFINED;
+  };
 
-  match match_pattern(value, &patterns, None) {
-    Ok(result) => result,
-    Err(_) => JsValue::undefined(),
+  if matches_key(value, &get_string_value(pattern), &options) {
+    handler.call0(&JsValue::NULL).unwrap_or(JsValue::UNDEFINED)
+  } else {
+    JsValue::UNDEFINED
   }
 }
 
 #[wasm_bindgen]
-pub fn matches(value: &JsValue, pattern: &JsValue, options: Option<Object>) -> bool {
-  let pattern_str = get_string_value(pattern);
-
-  let patterns = Object::new();
-  let _ = Reflect::set(
-    &patterns,
-    &JsValue::from_str(&pattern_str),
-    &Function::new_no_args("return true;"),
-  );
-  let _ = Reflect::set(
-    &patterns,
-    &JsValue::from_str(DEFAULT_HANDLER),
-    &Function::new_no_args("return false;"),
-  );
-
-  match match_pattern(value, &patterns, options) {
-    Ok(result) => result.as_bool().unwrap(),
-    Err(_) => false,
-  }
+pub fn matches(
+  value: &JsValue,
+  pattern: &JsValue,
+  options: Option<Object>,
+) -> Result<bool, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  Ok(matches_key(value, &get_string_value(pattern), &options))
 }
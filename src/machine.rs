@@ -0,0 +1,177 @@
+use crate::tuple::{describe_tuple_key, TupleTable};
+use crate::{call_handler, describe_value, MatchOptions};
+use js_sys::{Array, Function, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+fn machine_error(code: &str, message: &str, fields: &[(&str, &JsValue)]) -> JsValue {
+  let error = js_sys::Error::new(message);
+  error.set_name("MachineError");
+  let _ = Reflect::set(&error, &JsValue::from_str("code"), &JsValue::from_str(code));
+  for (name, value) in fields {
+    let _ = Reflect::set(&error, &JsValue::from_str(name), value);
+  }
+  error.into()
+}
+
+fn invalid_config(message: &str) -> JsValue {
+  machine_error("invalid-config", message, &[])
+}
+
+fn get_object(config: &Object, name: &str) -> Result<Object, JsValue> {
+  Reflect::get(config, &JsValue::from_str(name))
+    .ok()
+    .filter(|value| value.is_object())
+    .map(|value| value.unchecked_into())
+    .ok_or_else(|| invalid_config(&format!("createMachine() requires a `{}` object", name)))
+}
+
+#[wasm_bindgen]
+pub struct Machine {
+  states: Object,
+  transitions: TupleTable,
+  options: MatchOptions,
+  current: String,
+}
+
+impl Machine {
+  fn has_state(&self, name: &str) -> bool {
+    self.states.has_own_property(&JsValue::from_str(name))
+  }
+
+  fn run_action(&self, state: &str, action: &str, transition: &Object) -> Result<(), JsValue> {
+    let node = Reflect::get(&self.states, &JsValue::from_str(state))?;
+    if !node.is_object() {
+      return Ok(());
+    }
+    if let Some(action) = Reflect::get(&node, &JsValue::from_str(action))?.dyn_ref::<Function>() {
+      action.call2(&JsValue::NULL, transition, &self.options.context)?;
+    }
+    Ok(())
+  }
+}
+
+#[wasm_bindgen]
+impl Machine {
+  #[wasm_bindgen(getter)]
+  pub fn state(&self) -> String {
+    self.current.clone()
+  }
+
+  pub fn can(&self, event: &JsValue) -> bool {
+    let values = [JsValue::from_str(&self.current), event.clone()];
+    self.transitions.find(&values, &self.options).is_some()
+  }
+
+  pub fn send(&mut self, event: &JsValue) -> Result<String, JsValue> {
+    let from = JsValue::from_str(&self.current);
+    let values = [from.clone(), event.clone()];
+    let Some(handler) = self.transitions.find(&values, &self.options) else {
+      return Err(machine_error(
+        "invalid-transition",
+        &format!(
+          "No transition from state `{}` on event {}",
+          self.current,
+          describe_value(event)
+        ),
+        &[("state", &from), ("event", event)],
+      ));
+    };
+
+    let target = call_handler(handler, &Array::of2(&from, event), &self.options)?;
+    let to = match target.as_string() {
+      Some(to) if self.has_state(&to) => to,
+      _ => {
+        return Err(machine_error(
+          "unknown-state",
+          &format!(
+            "Transition from state `{}` on event {} targets unknown state {}",
+            self.current,
+            describe_value(event),
+            describe_value(&target)
+          ),
+          &[("state", &from), ("event", event), ("target", &target)],
+        ))
+      }
+    };
+
+    let transition = Object::new();
+    Reflect::set(&transition, &JsValue::from_str("from"), &from)?;
+    Reflect::set(
+      &transition,
+      &JsValue::from_str("to"),
+      &JsValue::from_str(&to),
+    )?;
+    Reflect::set(&transition, &JsValue::from_str("event"), event)?;
+
+    self.run_action(&self.current, "exit", &transition)?;
+    self.current = to;
+    self.run_action(&self.current, "entry", &transition)?;
+    Ok(self.current.clone())
+  }
+}
+
+#[wasm_bindgen(js_name = "createMachine")]
+pub fn create_machine(config: &Object) -> Result<Machine, JsValue> {
+  let states = get_object(config, "states")?;
+  let transitions = get_object(config, "transitions")?;
+  let has_state = |name: &str| states.has_own_property(&JsValue::from_str(name));
+
+  let initial = Reflect::get(config, &JsValue::from_str("initial"))?;
+  let current = if initial.is_undefined() {
+    Object::keys(&states)
+      .get(0)
+      .as_string()
+      .ok_or_else(|| invalid_config("createMachine() requires at least one state"))?
+  } else {
+    match initial.as_string() {
+      Some(name) if has_state(&name) => name,
+      _ => {
+        return Err(machine_error(
+          "unknown-state",
+          &format!("Initial state {} is not declared", describe_value(&initial)),
+          &[("target", &initial)],
+        ))
+      }
+    }
+  };
+
+  let case_sensitive = Reflect::get(config, &JsValue::from_str("caseSensitive"))?;
+  let options = MatchOptions {
+    constants: true,
+    case_sensitive: case_sensitive.is_undefined() || case_sensitive.is_truthy(),
+    ..MatchOptions::from_js(Some(config))?
+  };
+  let to_config_error = |err: JsValue| match err.as_string() {
+    Some(message) => invalid_config(&message),
+    None => err,
+  };
+  let table = TupleTable::compile(&transitions, &options).map_err(to_config_error)?;
+  table.check_arity(2).map_err(to_config_error)?;
+
+  for (key, handler) in table.handlers() {
+    if handler.is_function() {
+      continue;
+    }
+    match handler.as_string() {
+      Some(target) if has_state(&target) => {}
+      _ => {
+        return Err(machine_error(
+          "unknown-state",
+          &format!(
+            "Transition {} targets unknown state {}",
+            describe_tuple_key(key),
+            describe_value(handler)
+          ),
+          &[("target", handler)],
+        ))
+      }
+    }
+  }
+
+  Ok(Machine {
+    states,
+    transitions: table,
+    options,
+    current,
+  })
+}
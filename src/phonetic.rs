@@ -0,0 +1,731 @@
+use crate::{is_combining_mark, PREFIX_PHONETIC};
+use js_sys::JsString;
+use wasm_bindgen::prelude::*;
+
+const METAPHONE_LENGTH: usize = 4;
+
+#[derive(Clone, Copy)]
+enum Algorithm {
+  Soundex,
+  DoubleMetaphone,
+}
+
+impl Algorithm {
+  fn parse(name: &str) -> Option<Self> {
+    match name {
+      "soundex" => Some(Algorithm::Soundex),
+      "doubleMetaphone" => Some(Algorithm::DoubleMetaphone),
+      _ => None,
+    }
+  }
+
+  fn as_str(self) -> &'static str {
+    match self {
+      Algorithm::Soundex => "soundex",
+      Algorithm::DoubleMetaphone => "doubleMetaphone",
+    }
+  }
+
+  fn encode(self, text: &str) -> Vec<String> {
+    let letters = prepare(text);
+    let codes = match self {
+      Algorithm::Soundex => vec![soundex(&letters)],
+      Algorithm::DoubleMetaphone => {
+        let (primary, alternate) = DoubleMetaphone::new(&letters).encode();
+        if primary == alternate {
+          vec![primary]
+        } else {
+          vec![primary, alternate]
+        }
+      }
+    };
+    codes.into_iter().filter(|code| !code.is_empty()).collect()
+  }
+}
+
+fn prepare(text: &str) -> Vec<char> {
+  let decomposed = String::from(JsString::from(text.trim()).normalize("NFD"));
+  let mut letters: Vec<char> = Vec::with_capacity(decomposed.len());
+  for c in decomposed.chars().flat_map(char::to_uppercase) {
+    match (letters.last(), c) {
+      (Some('C'), '\u{0327}') => *letters.last_mut().unwrap() = 'Ç',
+      (Some('N'), '\u{0303}') => *letters.last_mut().unwrap() = 'Ñ',
+      (_, c) if is_combining_mark(c) => {}
+      (_, c) => letters.push(c),
+    }
+  }
+  letters
+}
+
+#[wasm_bindgen(js_name = "soundsLike")]
+pub fn sounds_like(name: &str, algorithm: Option<String>) -> Result<String, JsValue> {
+  let algorithm = match algorithm.as_deref() {
+    None => Algorithm::DoubleMetaphone,
+    Some(name) => Algorithm::parse(name).ok_or_else(|| {
+      JsValue::from_str("soundsLike() algorithm must be either 'soundex' or 'doubleMetaphone'")
+    })?,
+  };
+  let codes = algorithm.encode(name);
+  if codes.is_empty() {
+    return Err(JsValue::from_str(&format!(
+      "soundsLike() cannot encode {:?}; it needs at least one letter",
+      name
+    )));
+  }
+  Ok(format!(
+    "{}{}::{}",
+    PREFIX_PHONETIC,
+    algorithm.as_str(),
+    codes.join("|")
+  ))
+}
+
+fn parse_phonetic_key(pattern: &str) -> Option<(Algorithm, Vec<&str>)> {
+  let (algorithm, codes) = pattern.strip_prefix(PREFIX_PHONETIC)?.split_once("::")?;
+  let codes: Vec<&str> = codes.split('|').collect();
+  (!codes.iter().any(|code| code.is_empty()))
+    .then(|| Algorithm::parse(algorithm).map(|algorithm| (algorithm, codes)))
+    .flatten()
+}
+
+pub(crate) fn is_phonetic_key(pattern: &str) -> bool {
+  parse_phonetic_key(pattern).is_some()
+}
+
+pub(crate) fn phonetic_matches(pattern: &str, value: &JsValue) -> bool {
+  let (Some(text), Some((algorithm, codes))) = (value.as_string(), parse_phonetic_key(pattern))
+  else {
+    return false;
+  };
+  algorithm
+    .encode(&text)
+    .iter()
+    .any(|code| codes.contains(&code.as_str()))
+}
+
+fn soundex_digit(c: char) -> Option<char> {
+  match c {
+    'B' | 'F' | 'P' | 'V' => Some('1'),
+    'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+    'D' | 'T' => Some('3'),
+    'L' => Some('4'),
+    'M' | 'N' => Some('5'),
+    'R' => Some('6'),
+    _ => None,
+  }
+}
+
+fn soundex(letters: &[char]) -> String {
+  let mut letters = letters.iter().copied().filter(char::is_ascii_uppercase);
+  let Some(first) = letters.next() else {
+    return String::new();
+  };
+
+  let mut code = String::from(first);
+  let mut last = soundex_digit(first);
+  for c in letters {
+    let digit = soundex_digit(c);
+    if let Some(digit) = digit.filter(|&digit| last != Some(digit)) {
+      code.push(digit);
+    }
+    if code.len() == 4 {
+      break;
+    }
+    if c != 'H' && c != 'W' {
+      last = digit;
+    }
+  }
+  while code.len() < 4 {
+    code.push('0');
+  }
+  code
+}
+
+// Lawrence Philips' Double Metaphone, following his original implementation.
+struct DoubleMetaphone<'a> {
+  word: &'a [char],
+  slavo_germanic: bool,
+  primary: String,
+  alternate: String,
+}
+
+fn is_vowel(c: char) -> bool {
+  matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+impl<'a> DoubleMetaphone<'a> {
+  fn new(word: &'a [char]) -> Self {
+    let text: String = word.iter().collect();
+    Self {
+      word,
+      slavo_germanic: text.contains('W')
+        || text.contains('K')
+        || text.contains("CZ")
+        || text.contains("WITZ"),
+      primary: String::new(),
+      alternate: String::new(),
+    }
+  }
+
+  fn at(&self, index: isize) -> char {
+    usize::try_from(index)
+      .ok()
+      .and_then(|index| self.word.get(index))
+      .copied()
+      .unwrap_or('\0')
+  }
+
+  fn last(&self) -> isize {
+    self.word.len() as isize - 1
+  }
+
+  fn has(&self, start: isize, candidates: &[&str]) -> bool {
+    let Ok(start) = usize::try_from(start) else {
+      return false;
+    };
+    candidates.iter().any(|candidate| {
+      let length = candidate.chars().count();
+      start + length <= self.word.len()
+        && self.word[start..start + length]
+          .iter()
+          .copied()
+          .eq(candidate.chars())
+    })
+  }
+
+  fn push_primary(&mut self, code: &str) {
+    for c in code.chars() {
+      if self.primary.len() < METAPHONE_LENGTH {
+        self.primary.push(c);
+      }
+    }
+  }
+
+  fn push_alternate(&mut self, code: &str) {
+    for c in code.chars() {
+      if self.alternate.len() < METAPHONE_LENGTH {
+        self.alternate.push(c);
+      }
+    }
+  }
+
+  fn push(&mut self, code: &str) {
+    self.push_both(code, code);
+  }
+
+  fn push_both(&mut self, primary: &str, alternate: &str) {
+    self.push_primary(primary);
+    self.push_alternate(alternate);
+  }
+
+  fn complete(&self) -> bool {
+    self.primary.len() >= METAPHONE_LENGTH && self.alternate.len() >= METAPHONE_LENGTH
+  }
+
+  fn skip_double(&self, index: isize, c: char) -> isize {
+    if self.at(index + 1) == c {
+      index + 2
+    } else {
+      index + 1
+    }
+  }
+
+  fn encode(mut self) -> (String, String) {
+    let mut index: isize = 0;
+    if self.has(0, &["GN", "KN", "PN", "WR", "PS"]) {
+      index = 1;
+    }
+
+    while !self.complete() && index <= self.last() {
+      index = match self.at(index) {
+        'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+          if index == 0 {
+            self.push("A");
+          }
+          index + 1
+        }
+        'B' => {
+          self.push("P");
+          self.skip_double(index, 'B')
+        }
+        'Ç' => {
+          self.push("S");
+          index + 1
+        }
+        'C' => self.c(index),
+        'D' => self.d(index),
+        'F' => {
+          self.push("F");
+          self.skip_double(index, 'F')
+        }
+        'G' => self.g(index),
+        'H' => self.h(index),
+        'J' => self.j(index),
+        'K' => {
+          self.push("K");
+          self.skip_double(index, 'K')
+        }
+        'L' => self.l(index),
+        'M' => {
+          self.push("M");
+          if self.m_is_doubled(index) {
+            index + 2
+          } else {
+            index + 1
+          }
+        }
+        'N' => {
+          self.push("N");
+          self.skip_double(index, 'N')
+        }
+        'Ñ' => {
+          self.push("N");
+          index + 1
+        }
+        'P' => self.p(index),
+        'Q' => {
+          self.push("K");
+          self.skip_double(index, 'Q')
+        }
+        'R' => self.r(index),
+        'S' => self.s(index),
+        'T' => self.t(index),
+        'V' => {
+          self.push("F");
+          self.skip_double(index, 'V')
+        }
+        'W' => self.w(index),
+        'X' => self.x(index),
+        'Z' => self.z(index),
+        _ => index + 1,
+      };
+    }
+
+    (self.primary, self.alternate)
+  }
+
+  fn m_is_doubled(&self, index: isize) -> bool {
+    self.at(index + 1) == 'M'
+      || (self.has(index - 1, &["UMB"])
+        && (index + 1 == self.last() || self.has(index + 2, &["ER"])))
+  }
+
+  fn c(&mut self, index: isize) -> isize {
+    if self.c_is_germanic_k(index) {
+      self.push("K");
+      index + 2
+    } else if index == 0 && self.has(index, &["CAESAR"]) {
+      self.push("S");
+      index + 2
+    } else if self.has(index, &["CH"]) {
+      self.ch(index)
+    } else if self.has(index, &["CZ"]) && !self.has(index - 2, &["WICZ"]) {
+      self.push_both("S", "X");
+      index + 2
+    } else if self.has(index + 1, &["CIA"]) {
+      self.push("X");
+      index + 3
+    } else if self.has(index, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+      self.cc(index)
+    } else if self.has(index, &["CK", "CG", "CQ"]) {
+      self.push("K");
+      index + 2
+    } else if self.has(index, &["CI", "CE", "CY"]) {
+      if self.has(index, &["CIO", "CIE", "CIA"]) {
+        self.push_both("S", "X");
+      } else {
+        self.push("S");
+      }
+      index + 2
+    } else {
+      self.push("K");
+      if self.has(index + 1, &[" C", " Q", " G"]) {
+        index + 3
+      } else if self.has(index + 1, &["C", "K", "Q"]) && !self.has(index + 1, &["CE", "CI"]) {
+        index + 2
+      } else {
+        index + 1
+      }
+    }
+  }
+
+  fn c_is_germanic_k(&self, index: isize) -> bool {
+    if self.has(index, &["CHIA"]) {
+      true
+    } else if index <= 1 || is_vowel(self.at(index - 2)) || !self.has(index - 1, &["ACH"]) {
+      false
+    } else {
+      let next = self.at(index + 2);
+      (next != 'I' && next != 'E') || self.has(index - 2, &["BACHER", "MACHER"])
+    }
+  }
+
+  fn cc(&mut self, index: isize) -> isize {
+    if self.has(index + 2, &["I", "E", "H"]) && !self.has(index + 2, &["HU"]) {
+      if (index == 1 && self.at(index - 1) == 'A') || self.has(index - 1, &["UCCEE", "UCCES"]) {
+        self.push("KS");
+      } else {
+        self.push("X");
+      }
+      index + 3
+    } else {
+      self.push("K");
+      index + 2
+    }
+  }
+
+  fn ch(&mut self, index: isize) -> isize {
+    if index > 0 && self.has(index, &["CHAE"]) {
+      self.push_both("K", "X");
+    } else if self.ch_is_greek_k(index) || self.ch_is_germanic_k(index) {
+      self.push("K");
+    } else if index > 0 {
+      if self.has(0, &["MC"]) {
+        self.push("K");
+      } else {
+        self.push_both("X", "K");
+      }
+    } else {
+      self.push("X");
+    }
+    index + 2
+  }
+
+  fn ch_is_greek_k(&self, index: isize) -> bool {
+    index == 0
+      && (self.has(index + 1, &["HARAC", "HARIS"])
+        || self.has(index + 1, &["HOR", "HYM", "HIA", "HEM"]))
+      && !self.has(0, &["CHORE"])
+  }
+
+  fn ch_is_germanic_k(&self, index: isize) -> bool {
+    self.has(0, &["VAN ", "VON ", "SCH"])
+      || self.has(index - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+      || self.has(index + 2, &["T", "S"])
+      || ((self.has(index - 1, &["A", "O", "U", "E"]) || index == 0)
+        && (self.has(
+          index + 2,
+          &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+        ) || index + 1 == self.last()))
+  }
+
+  fn d(&mut self, index: isize) -> isize {
+    if self.has(index, &["DG"]) {
+      if self.has(index + 2, &["I", "E", "Y"]) {
+        self.push("J");
+        index + 3
+      } else {
+        self.push("TK");
+        index + 2
+      }
+    } else if self.has(index, &["DT", "DD"]) {
+      self.push("T");
+      index + 2
+    } else {
+      self.push("T");
+      index + 1
+    }
+  }
+
+  fn g(&mut self, index: isize) -> isize {
+    let next = self.at(index + 1);
+    if next == 'H' {
+      return self.gh(index);
+    }
+    if next == 'N' {
+      if index == 1 && is_vowel(self.at(0)) && !self.slavo_germanic {
+        self.push_both("KN", "N");
+      } else if !self.has(index + 2, &["EY"]) && !self.slavo_germanic {
+        self.push_both("N", "KN");
+      } else {
+        self.push("KN");
+      }
+      return index + 2;
+    }
+    if self.has(index + 1, &["LI"]) && !self.slavo_germanic {
+      self.push_both("KL", "L");
+      return index + 2;
+    }
+    if index == 0
+      && (next == 'Y'
+        || self.has(
+          index + 1,
+          &[
+            "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+          ],
+        ))
+    {
+      self.push_both("K", "J");
+      return index + 2;
+    }
+    if (self.has(index + 1, &["ER"]) || next == 'Y')
+      && !self.has(0, &["DANGER", "RANGER", "MANGER"])
+      && !self.has(index - 1, &["E", "I"])
+      && !self.has(index - 1, &["RGY", "OGY"])
+    {
+      self.push_both("K", "J");
+      return index + 2;
+    }
+    if self.has(index + 1, &["E", "I", "Y"]) || self.has(index - 1, &["AGGI", "OGGI"]) {
+      if self.has(0, &["VAN ", "VON ", "SCH"]) || self.has(index + 1, &["ET"]) {
+        self.push("K");
+      } else if self.has(index + 1, &["IER"]) {
+        self.push("J");
+      } else {
+        self.push_both("J", "K");
+      }
+      return index + 2;
+    }
+    self.push("K");
+    self.skip_double(index, 'G')
+  }
+
+  fn gh(&mut self, index: isize) -> isize {
+    if index > 0 && !is_vowel(self.at(index - 1)) {
+      self.push("K");
+    } else if index == 0 {
+      if self.at(index + 2) == 'I' {
+        self.push("J");
+      } else {
+        self.push("K");
+      }
+    } else if (index > 1 && self.has(index - 2, &["B", "H", "D"]))
+      || (index > 2 && self.has(index - 3, &["B", "H", "D"]))
+      || (index > 3 && self.has(index - 4, &["B", "H"]))
+    {
+    } else if index > 2
+      && self.at(index - 1) == 'U'
+      && self.has(index - 3, &["C", "G", "L", "R", "T"])
+    {
+      self.push("F");
+    } else if self.at(index - 1) != 'I' {
+      self.push("K");
+    }
+    index + 2
+  }
+
+  fn h(&mut self, index: isize) -> isize {
+    if (index == 0 || is_vowel(self.at(index - 1))) && is_vowel(self.at(index + 1)) {
+      self.push("H");
+      index + 2
+    } else {
+      index + 1
+    }
+  }
+
+  fn j(&mut self, index: isize) -> isize {
+    if self.has(index, &["JOSE"]) || self.has(0, &["SAN "]) {
+      if (index == 0 && self.at(index + 4) == ' ') || self.word.len() == 4 || self.has(0, &["SAN "])
+      {
+        self.push("H");
+      } else {
+        self.push_both("J", "H");
+      }
+      return index + 1;
+    }
+
+    if index == 0 {
+      self.push_both("J", "A");
+    } else if is_vowel(self.at(index - 1))
+      && !self.slavo_germanic
+      && matches!(self.at(index + 1), 'A' | 'O')
+    {
+      self.push_both("J", "H");
+    } else if index == self.last() {
+      self.push_both("J", " ");
+    } else if !self.has(index + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+      && !self.has(index - 1, &["S", "K", "L"])
+    {
+      self.push("J");
+    }
+    self.skip_double(index, 'J')
+  }
+
+  fn l(&mut self, index: isize) -> isize {
+    if self.at(index + 1) != 'L' {
+      self.push("L");
+      return index + 1;
+    }
+    let length = self.word.len() as isize;
+    let spanish = (index == length - 3 && self.has(index - 1, &["ILLO", "ILLA", "ALLE"]))
+      || ((self.has(length - 2, &["AS", "OS"]) || self.has(length - 1, &["A", "O"]))
+        && self.has(index - 1, &["ALLE"]));
+    if spanish {
+      self.push_primary("L");
+    } else {
+      self.push("L");
+    }
+    index + 2
+  }
+
+  fn p(&mut self, index: isize) -> isize {
+    if self.at(index + 1) == 'H' {
+      self.push("F");
+      return index + 2;
+    }
+    self.push("P");
+    if self.has(index + 1, &["P", "B"]) {
+      index + 2
+    } else {
+      index + 1
+    }
+  }
+
+  fn r(&mut self, index: isize) -> isize {
+    if index == self.last()
+      && !self.slavo_germanic
+      && self.has(index - 2, &["IE"])
+      && !self.has(index - 4, &["ME", "MA"])
+    {
+      self.push_alternate("R");
+    } else {
+      self.push("R");
+    }
+    self.skip_double(index, 'R')
+  }
+
+  fn s(&mut self, index: isize) -> isize {
+    if self.has(index - 1, &["ISL", "YSL"]) {
+      return index + 1;
+    }
+    if index == 0 && self.has(index, &["SUGAR"]) {
+      self.push_both("X", "S");
+      return index + 1;
+    }
+    if self.has(index, &["SH"]) {
+      if self.has(index + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+        self.push("S");
+      } else {
+        self.push("X");
+      }
+      return index + 2;
+    }
+    if self.has(index, &["SIO", "SIA", "SIAN"]) {
+      if self.slavo_germanic {
+        self.push("S");
+      } else {
+        self.push_both("S", "X");
+      }
+      return index + 3;
+    }
+    if (index == 0 && self.has(index + 1, &["M", "N", "L", "W"])) || self.has(index + 1, &["Z"]) {
+      self.push_both("S", "X");
+      return if self.has(index + 1, &["Z"]) {
+        index + 2
+      } else {
+        index + 1
+      };
+    }
+    if self.has(index, &["SC"]) {
+      return self.sc(index);
+    }
+    if index == self.last() && self.has(index - 2, &["AI", "OI"]) {
+      self.push_alternate("S");
+    } else {
+      self.push("S");
+    }
+    if self.has(index + 1, &["S", "Z"]) {
+      index + 2
+    } else {
+      index + 1
+    }
+  }
+
+  fn sc(&mut self, index: isize) -> isize {
+    if self.at(index + 2) == 'H' {
+      if self.has(index + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+        if self.has(index + 3, &["ER", "EN"]) {
+          self.push_both("X", "SK");
+        } else {
+          self.push("SK");
+        }
+      } else if index == 0 && !is_vowel(self.at(3)) && self.at(3) != 'W' {
+        self.push_both("X", "S");
+      } else {
+        self.push("X");
+      }
+    } else if self.has(index + 2, &["I", "E", "Y"]) {
+      self.push("S");
+    } else {
+      self.push("SK");
+    }
+    index + 3
+  }
+
+  fn t(&mut self, index: isize) -> isize {
+    if self.has(index, &["TION", "TIA", "TCH"]) {
+      self.push("X");
+      return index + 3;
+    }
+    if self.has(index, &["TH", "TTH"]) {
+      if self.has(index + 2, &["OM", "AM"]) || self.has(0, &["VAN ", "VON ", "SCH"]) {
+        self.push("T");
+      } else {
+        self.push_both("0", "T");
+      }
+      return index + 2;
+    }
+    self.push("T");
+    if self.has(index + 1, &["T", "D"]) {
+      index + 2
+    } else {
+      index + 1
+    }
+  }
+
+  fn w(&mut self, index: isize) -> isize {
+    if self.has(index, &["WR"]) {
+      self.push("R");
+      return index + 2;
+    }
+    let next_is_vowel = is_vowel(self.at(index + 1));
+    if index == 0 && (next_is_vowel || self.has(index, &["WH"])) {
+      if next_is_vowel {
+        self.push_both("A", "F");
+      } else {
+        self.push("A");
+      }
+    } else if (index == self.last() && is_vowel(self.at(index - 1)))
+      || self.has(index - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+      || self.has(0, &["SCH"])
+    {
+      self.push_alternate("F");
+    } else if self.has(index, &["WICZ", "WITZ"]) {
+      self.push_both("TS", "FX");
+      return index + 4;
+    }
+    index + 1
+  }
+
+  fn x(&mut self, index: isize) -> isize {
+    if index == 0 {
+      self.push("S");
+      return index + 1;
+    }
+    let silent = index == self.last()
+      && (self.has(index - 3, &["IAU", "EAU"]) || self.has(index - 2, &["AU", "OU"]));
+    if !silent {
+      self.push("KS");
+    }
+    if self.has(index + 1, &["C", "X"]) {
+      index + 2
+    } else {
+      index + 1
+    }
+  }
+
+  fn z(&mut self, index: isize) -> isize {
+    if self.at(index + 1) == 'H' {
+      self.push("J");
+      return index + 2;
+    }
+    if self.has(index + 1, &["ZO", "ZI", "ZA"])
+      || (self.slavo_germanic && index > 0 && self.at(index - 1) != 'T')
+    {
+      self.push_both("S", "TS");
+    } else {
+      self.push("S");
+    }
+    self.skip_double(index, 'Z')
+  }
+}
@@ -0,0 +1,165 @@
+use crate::{collision_error, ArmKind, MatchOptions, PatternGroups, PatternTable};
+use js_sys::{Array, Function, Object, Reflect};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+#[derive(Default)]
+struct Subscriptions {
+  patterns: Object,
+  groups: PatternGroups,
+  handlers: HashMap<u32, (String, Function)>,
+  next_id: u32,
+}
+
+impl Subscriptions {
+  fn subscribers(&self, key: &str) -> Option<Array> {
+    let key = JsValue::from_str(key);
+    if !self.patterns.has_own_property(&key) {
+      return None;
+    }
+    Reflect::get(&self.patterns, &key)
+      .ok()
+      .map(|subscribers| subscribers.unchecked_into())
+  }
+
+  fn collision_with(&self, pattern: &str, options: &MatchOptions) -> Option<&String> {
+    if !options.canonicalizes()
+      || !matches!(ArmKind::of(pattern), ArmKind::Exact | ArmKind::Literal)
+    {
+      return None;
+    }
+    self
+      .groups
+      .folded_keys(&options.canonical_key(pattern))
+      .first()
+  }
+}
+
+#[wasm_bindgen]
+pub struct Router {
+  options: MatchOptions,
+  subscriptions: RefCell<Subscriptions>,
+}
+
+impl Router {
+  fn modify(&self) -> Result<std::cell::RefMut<'_, Subscriptions>, JsValue> {
+    self.subscriptions.try_borrow_mut().map_err(|_| {
+      JsValue::from_str("Router subscriptions cannot change while a pattern is being matched")
+    })
+  }
+}
+
+#[wasm_bindgen]
+impl Router {
+  #[wasm_bindgen(constructor)]
+  pub fn new(options: Option<Object>) -> Result<Router, JsValue> {
+    Ok(Router {
+      options: MatchOptions {
+        constants: true,
+        ..MatchOptions::from_js(options.as_ref())?
+      },
+      subscriptions: RefCell::default(),
+    })
+  }
+
+  pub fn on(&self, pattern: &str, handler: Function) -> Result<u32, JsValue> {
+    let mut subscriptions = self.modify()?;
+    let id = subscriptions.next_id;
+    subscriptions.next_id += 1;
+
+    let subscribers = match subscriptions.subscribers(pattern) {
+      Some(subscribers) => subscribers,
+      None => {
+        if let Some(existing) = subscriptions.collision_with(pattern, &self.options) {
+          return Err(collision_error(&[existing.clone(), pattern.to_string()]));
+        }
+        let subscribers = Array::new();
+        Reflect::set(
+          &subscriptions.patterns,
+          &JsValue::from_str(pattern),
+          &subscribers,
+        )?;
+        subscriptions.groups.insert(
+          pattern.to_string(),
+          subscribers.clone().into(),
+          &self.options,
+        );
+        subscribers
+      }
+    };
+    subscribers.push(&JsValue::from(id));
+    subscriptions
+      .handlers
+      .insert(id, (pattern.to_string(), handler));
+    Ok(id)
+  }
+
+  pub fn off(&self, id: u32) -> Result<bool, JsValue> {
+    let mut subscriptions = self.modify()?;
+    let Some((pattern, _)) = subscriptions.handlers.remove(&id) else {
+      return Ok(false);
+    };
+
+    if let Some(subscribers) = subscriptions.subscribers(&pattern) {
+      let index = subscribers.index_of(&JsValue::from(id), 0);
+      if index >= 0 {
+        subscribers.copy_within(index, index + 1, subscribers.length() as i32);
+        subscribers.pop();
+      }
+      if subscribers.length() == 0 {
+        Reflect::delete_property(&subscriptions.patterns, &JsValue::from_str(&pattern))?;
+        subscriptions.groups.remove(&pattern, &self.options);
+      }
+    }
+    Ok(true)
+  }
+
+  pub fn dispatch(&self, value: &JsValue, context: JsValue) -> Result<Array, JsValue> {
+    let options = MatchOptions {
+      context: if context.is_undefined() {
+        self.options.context.clone()
+      } else {
+        context
+      },
+      ..self.options
+    };
+
+    let (handlers, distance): (Vec<Function>, Option<usize>) = {
+      let subscriptions = self.subscriptions.borrow();
+      let table =
+        PatternTable::with_groups(&subscriptions.patterns, &options, &subscriptions.groups);
+      let Some(arm) = table.find(value, true) else {
+        return Ok(Array::new());
+      };
+      let distance = arm.distance(value, &options);
+      let handlers = arm
+        .handler
+        .unchecked_into::<Array>()
+        .iter()
+        .filter_map(|id| {
+          let id = id.as_f64()? as u32;
+          subscriptions
+            .handlers
+            .get(&id)
+            .map(|(_, handler)| handler.clone())
+        })
+        .collect();
+      (handlers, distance)
+    };
+
+    let results = Array::new();
+    for handler in &handlers {
+      results.push(&match distance {
+        Some(distance) => handler.call3(
+          &JsValue::NULL,
+          value,
+          &options.context,
+          &JsValue::from(distance as u32),
+        )?,
+        None => handler.call2(&JsValue::NULL, value, &options.context)?,
+      });
+    }
+    Ok(results)
+  }
+}
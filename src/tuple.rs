@@ -0,0 +1,263 @@
+use crate::{
+  call_handler, decode_value, describe_value, effective_regex_flags, encode_value, encoded_matches,
+  get_handler, get_string_value, parse_regex_key, regex_test, try_compile_regex, wildcard_test,
+  wildcard_to_regex, ArmKind, MatchOptions, DEFAULT_HANDLER, PREFIX_LIT,
+};
+use js_sys::{Array, Object, RegExp};
+use wasm_bindgen::prelude::*;
+
+const PREFIX_TUPLE: &str = "tuple::";
+const TUPLE_SEP: char = '\x1E';
+const TAG_VALUE: char = 'v';
+const TAG_PATTERN: char = 'p';
+
+#[wasm_bindgen]
+pub fn tuple(args: &Array) -> Result<String, JsValue> {
+  let length = args.length();
+
+  if length == 0 {
+    return Err(JsValue::from_str(
+      "tuple() requires at least one position pattern",
+    ));
+  }
+
+  let mut positions = Vec::with_capacity(length as usize);
+  for arg in args.iter() {
+    let position = match arg.as_string() {
+      Some(key) if key.starts_with(PREFIX_TUPLE) || key.contains(TUPLE_SEP) => {
+        return Err(JsValue::from_str(
+          "tuple() positions cannot contain nested tuples",
+        ))
+      }
+      Some(key) if ArmKind::of(&key) == ArmKind::Literal => {
+        format!("{}{}", TAG_VALUE, &key[PREFIX_LIT.len()..])
+      }
+      Some(key) if ArmKind::of(&key) != ArmKind::Exact => format!("{}{}", TAG_PATTERN, key),
+      _ => format!("{}{}", TAG_VALUE, encode_value(&arg)?),
+    };
+    positions.push(position);
+  }
+
+  Ok(format!(
+    "{}{}",
+    PREFIX_TUPLE,
+    positions.join(&TUPLE_SEP.to_string())
+  ))
+}
+
+fn positions(key: &str) -> Option<impl Iterator<Item = &str>> {
+  key
+    .strip_prefix(PREFIX_TUPLE)
+    .map(|payload| payload.split(TUPLE_SEP))
+}
+
+fn describe_position(position: &str) -> String {
+  match position.strip_prefix(TAG_VALUE) {
+    Some(encoded) => decode_value(encoded)
+      .map(|value| describe_value(&value))
+      .unwrap_or_else(|| encoded.to_string()),
+    None => position
+      .strip_prefix(TAG_PATTERN)
+      .unwrap_or(position)
+      .to_string(),
+  }
+}
+
+pub(crate) fn describe_tuple_key(key: &str) -> String {
+  match positions(key) {
+    Some(positions) => format!(
+      "tuple({})",
+      positions
+        .map(describe_position)
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+    None => key.to_string(),
+  }
+}
+
+enum Position {
+  Value(String),
+  Regex(RegExp),
+  Wildcard(RegExp),
+  Arm(ArmKind, String),
+}
+
+impl Position {
+  fn compile(position: &str, options: &MatchOptions) -> Result<Self, String> {
+    let mut chars = position.chars();
+    let tag = chars.next();
+    let payload = chars.as_str();
+    match tag {
+      Some(TAG_VALUE) if decode_value(payload).is_some() => {
+        Ok(Position::Value(payload.to_string()))
+      }
+      Some(TAG_PATTERN) => match ArmKind::of(payload) {
+        ArmKind::Regex => {
+          let (pattern, flags) = parse_regex_key(payload)
+            .ok_or_else(|| format!("`{}` is not a valid regex", payload))?;
+          try_compile_regex(
+            &pattern,
+            &effective_regex_flags(flags, options.case_sensitive),
+          )
+          .map(Position::Regex)
+          .map_err(|_| format!("`{}` does not compile", payload))
+        }
+        ArmKind::Wildcard => Ok(Position::Wildcard(wildcard_to_regex(payload, options))),
+        ArmKind::Exact | ArmKind::Literal => Err(format!("`{}` is not a pattern", payload)),
+        kind => Ok(Position::Arm(kind, payload.to_string())),
+      },
+      _ => Err(format!("cannot decode position {:?}", position)),
+    }
+  }
+
+  fn matches(&self, value: &JsValue, options: &MatchOptions) -> bool {
+    match self {
+      Position::Value(encoded) => encoded_matches(encoded, value, options),
+      Position::Regex(regex) => regex_test(regex, &get_string_value(value), options),
+      Position::Wildcard(regex) => wildcard_test(regex, value, options),
+      Position::Arm(kind, key) => kind.matches(key, value, options, true),
+    }
+  }
+}
+
+pub(crate) struct TuplePattern {
+  positions: Vec<Position>,
+}
+
+impl TuplePattern {
+  pub(crate) fn compile(key: &str, options: &MatchOptions) -> Result<Self, JsValue> {
+    let positions = positions(key)
+      .ok_or_else(|| JsValue::from_str(&format!("Pattern `{}` was not created with tuple()", key)))?
+      .map(|position| Position::compile(position, options))
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|reason| {
+        JsValue::from_str(&format!(
+          "Invalid tuple pattern {}: {}",
+          describe_tuple_key(key),
+          reason
+        ))
+      })?;
+    Ok(Self { positions })
+  }
+
+  pub(crate) fn arity(&self) -> usize {
+    self.positions.len()
+  }
+
+  pub(crate) fn matches(&self, values: &[JsValue], options: &MatchOptions) -> bool {
+    self.positions.len() == values.len()
+      && self
+        .positions
+        .iter()
+        .zip(values)
+        .all(|(position, value)| position.matches(value, options))
+  }
+}
+
+struct TupleArm {
+  key: String,
+  pattern: TuplePattern,
+  handler: JsValue,
+}
+
+pub(crate) struct TupleTable {
+  arms: Vec<TupleArm>,
+  default: Option<JsValue>,
+}
+
+impl TupleTable {
+  pub(crate) fn compile(patterns: &Object, options: &MatchOptions) -> Result<Self, JsValue> {
+    let mut arms = Vec::new();
+    for key in Object::keys(patterns)
+      .iter()
+      .filter_map(|key| key.as_string())
+    {
+      if key == DEFAULT_HANDLER {
+        continue;
+      }
+      let pattern = TuplePattern::compile(&key, options)?;
+      if let Some(handler) = get_handler(patterns, &key, options) {
+        arms.push(TupleArm {
+          key,
+          pattern,
+          handler,
+        });
+      }
+    }
+
+    Ok(Self {
+      arms,
+      default: get_handler(patterns, DEFAULT_HANDLER, options),
+    })
+  }
+
+  pub(crate) fn handlers(&self) -> impl Iterator<Item = (&str, &JsValue)> {
+    self
+      .arms
+      .iter()
+      .map(|arm| (arm.key.as_str(), &arm.handler))
+      .chain(
+        self
+          .default
+          .iter()
+          .map(|handler| (DEFAULT_HANDLER, handler)),
+      )
+  }
+
+  pub(crate) fn check_arity(&self, arity: usize) -> Result<(), JsValue> {
+    match self.arms.iter().find(|arm| arm.pattern.arity() != arity) {
+      Some(arm) => Err(JsValue::from_str(&format!(
+        "Pattern {} has {} positions but {} values were given",
+        describe_tuple_key(&arm.key),
+        arm.pattern.arity(),
+        arity
+      ))),
+      None => Ok(()),
+    }
+  }
+
+  pub(crate) fn find(&self, values: &[JsValue], options: &MatchOptions) -> Option<&JsValue> {
+    self
+      .arms
+      .iter()
+      .find(|arm| arm.pattern.matches(values, options))
+      .map(|arm| &arm.handler)
+      .or(self.default.as_ref())
+  }
+
+  fn no_match_error(&self, values: &[JsValue]) -> JsValue {
+    let attempted_patterns: Vec<String> = self
+      .arms
+      .iter()
+      .map(|arm| describe_tuple_key(&arm.key))
+      .collect();
+
+    JsValue::from_str(&format!(
+      "No pattern matched for: [{}]. Attempted patterns: {}",
+      values
+        .iter()
+        .map(describe_value)
+        .collect::<Vec<_>>()
+        .join(", "),
+      attempted_patterns.join(", ")
+    ))
+  }
+}
+
+#[wasm_bindgen(js_name = "matchTuple")]
+pub fn match_tuple(
+  values: &Array,
+  patterns: &Object,
+  options: Option<Object>,
+) -> Result<JsValue, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let table = TupleTable::compile(patterns, &options)?;
+  let values_vec = values.to_vec();
+  table.check_arity(values_vec.len())?;
+
+  match table.find(&values_vec, &options) {
+    Some(handler) => call_handler(handler, values, &options),
+    None => Err(table.no_match_error(&values_vec)),
+  }
+}
@@ -1,14 +1,300 @@
-export type MatchValue = string | number | boolean | null | undefined
+export type MatchValue = string | number | bigint | boolean | Date | null | undefined
 
-export type PatternHandler<R> = () => R
+/**
+ * Handlers of `fuzzy()` patterns also receive the edit distance of the match.
+ */
+export type PatternHandler<R, C = any> = (value: MatchValue, context: C, distance?: number) => R
 
-export type PatternMap<R> = {
+export type PatternMap<R, C = any> = {
   /**
    * The special key '_' is used for the default handler.
    */
-  [pattern: string]: PatternHandler<R>
+  [pattern: string]: PatternHandler<R, C>
+}
+
+export type AsyncPatternHandler<R, C = any> = (
+  value: MatchValue,
+  context: C,
+  distance?: number,
+) => R | PromiseLike<R>
+
+export type AsyncPatternMap<R, C = any> = {
+  /**
+   * The special key '_' is used for the default handler.
+   */
+  [pattern: string]: AsyncPatternHandler<R, C>
+}
+
+export type TuplePatternHandler<R, C = any> = (values: MatchValue[], context: C) => R
+
+export type TuplePatternMap<R, C = any> = {
+  /**
+   * Keys are created with `tuple()`; the special key '_' is used for the default handler.
+   */
+  [pattern: string]: TuplePatternHandler<R, C>
+}
+
+export type ConstantTuplePatternMap<R, C = any> = {
+  /**
+   * Non-function values are returned as-is when the `constants` option is enabled.
+   */
+  [pattern: string]: R | TuplePatternHandler<R, C>
+}
+
+export type ConstantPatternMap<R, C = any> = {
+  /**
+   * Non-function values are returned as-is when the `constants` option is enabled.
+   */
+  [pattern: string]: R | PatternHandler<R, C>
 }
 
 export type Options = {
+  /**
+   * When false, strings are compared after full Unicode case folding, so 'STRASSE' matches 'straße'.
+   */
   caseSensitive?: boolean
+  /**
+   * Return non-function pattern values directly instead of skipping them.
+   */
+  constants?: boolean
+  /**
+   * Passed as the second argument to `when` predicates and handlers.
+   */
+  context?: unknown
+  /**
+   * Only let plain keys match string values; typed keys are created with `lit()`.
+   */
+  strict?: boolean
+  /**
+   * Convert trimmed numeric strings and 'true'/'false' before exact, `lit`, `any` and `not` comparisons.
+   */
+  coerce?: boolean
+  /**
+   * How numbers are compared in `lit`, `any` and `not` patterns. `'sameValueZero'` treats `0` and `-0` as equal;
+   * `'sameValue'` follows `Object.is`. Both treat `NaN` as equal to itself. Any other value throws.
+   */
+  equality?: 'sameValueZero' | 'sameValue'
+  /**
+   * A language tag used for case folding. 'tr' and 'az' fold 'I' to 'ı' and 'İ' to 'i'; other tags use the default folding.
+   */
+  locale?: string
+  /**
+   * Transforms applied to values and to exact, `lit`, `any`, `not`, wildcard and string-predicate patterns before they
+   * are compared. Regular expressions are left as written.
+   */
+  normalize?: NormalizeOptions
+}
+
+export type NormalizeOptions = {
+  /**
+   * Remove leading and trailing whitespace.
+   */
+  trim?: boolean
+  /**
+   * The Unicode normalization form both sides are converted to. Any other value throws.
+   */
+  form?: 'NFC' | 'NFD' | 'NFKC' | 'NFKD'
+  /**
+   * Replace each run of whitespace with a single space.
+   */
+  collapseWhitespace?: boolean
+  /**
+   * Remove combining diacritical marks, so 'Zürich' compares equal to 'Zurich'.
+   */
+  stripDiacritics?: boolean
+}
+
+export type ConstantOptions = Options & { constants: true }
+
+/**
+ * The subset of `AbortSignal` that `matchAsync` relies on.
+ */
+export type AbortSignalLike = {
+  readonly aborted: boolean
+  readonly reason?: unknown
+  addEventListener(type: 'abort', listener: () => void): void
+  removeEventListener(type: 'abort', listener: () => void): void
+}
+
+export type AsyncOptions = Options & {
+  /**
+   * Rejects with a `MatchAbortError` once the signal is aborted.
+   */
+  signal?: AbortSignalLike
+  /**
+   * Maximum time in milliseconds to wait for a single `when` predicate.
+   */
+  timeout?: number
+  /**
+   * Whether `when` predicates are awaited one at a time or all started up front (default: 'sequential').
+   */
+  predicates?: 'sequential' | 'concurrent'
+}
+
+export type MatchAbortError = Error & {
+  name: 'MatchAbortError'
+  reason: 'abort' | 'timeout'
+  /**
+   * The key of the predicate or handler that was pending; absent when the signal was aborted before matching started.
+   */
+  key?: string
+  /**
+   * The signal's `reason`, when `reason` is 'abort'.
+   */
+  cause?: unknown
+}
+
+export type TypeName = 'string' | 'number' | 'bigint' | 'boolean' | 'date' | 'null' | 'undefined'
+
+/**
+ * `'UTC'` or a fixed offset from UTC such as `'+05:30'` or `'-08:00'`. Named zones are not supported, so results
+ * never depend on the host's time zone database.
+ */
+export type TimeZone = 'UTC' | 'Z' | `+${string}` | `-${string}`
+
+export type PhoneticAlgorithm = 'soundex' | 'doubleMetaphone'
+
+export type DiagnosticSeverity = 'error' | 'warning'
+
+export type DiagnosticCode =
+  | 'invalid-regex'
+  | 'malformed-key'
+  | 'duplicate-value'
+  | 'unreachable'
+  | 'shadowed'
+  | 'never-matches'
+  | 'non-function-handler'
+  | 'case-collision'
+
+export type Diagnostic = {
+  severity: DiagnosticSeverity
+  code: DiagnosticCode
+  /**
+   * The pattern key the diagnostic refers to.
+   */
+  key: string
+  message: string
+}
+
+export type ExhaustivenessReport = {
+  /**
+   * Whether every domain member is handled by some arm.
+   */
+  exhaustive: boolean
+  /**
+   * Domain members that no arm matches.
+   */
+  uncovered: MatchValue[]
+  /**
+   * Keys of arms that do not match any domain member.
+   */
+  unreachable: string[]
+}
+
+export type PatternKind =
+  | 'some'
+  | 'none'
+  | 'exact'
+  | 'when'
+  | 'any'
+  | 'not'
+  | 'range'
+  | 'date'
+  | 'cron'
+  | 'text'
+  | 'regex'
+  | 'wildcard'
+  | 'fuzzy'
+  | 'phonetic'
+  | 'type'
+
+export type MatchedPattern = {
+  /**
+   * The key of the winning pattern, as it appears in the pattern map.
+   */
+  key: string
+  kind: PatternKind
+}
+
+export type ScoredPattern = {
+  key: string
+  kind: PatternKind | 'default'
+  /**
+   * Higher is better: 1 for exact matches, between 0.5 and 1 for `fuzzy()` depending on the distance, between 0.1 and
+   * 0.5 for wildcards depending on how many literal characters they have, 0.1 for `some()` and type patterns, 0.5 for
+   * other kinds and 0 for the default handler.
+   */
+  score: number
+  /**
+   * The edit distance of a `fuzzy()` match.
+   */
+  distance?: number
+}
+
+export type BestMatch<R> = ScoredPattern & {
+  /**
+   * The value returned by the handler of the best-scoring pattern.
+   */
+  result: R
+}
+
+export type BestMatchOptions = Options & {
+  /**
+   * Replaces the default score of each candidate pattern; the candidate with the highest finite score wins.
+   */
+  score?: (value: MatchValue, candidate: ScoredPattern) => number
+}
+
+export type MachineTransition = {
+  from: string
+  to: string
+  event: MatchValue
+}
+
+export type StateNode<C = any> = {
+  /**
+   * Runs after the machine enters this state.
+   */
+  entry?: (transition: MachineTransition, context: C) => void
+  /**
+   * Runs before the machine leaves this state.
+   */
+  exit?: (transition: MachineTransition, context: C) => void
+}
+
+export type TransitionTarget<S extends string, C = any> =
+  | S
+  | ((values: [state: S, event: MatchValue], context: C) => S)
+
+export type MachineConfig<S extends string, C = any> = {
+  states: Record<S, StateNode<C>>
+  /**
+   * Keys are created with `tuple(statePattern, eventPattern)`; the special key '_' matches any transition.
+   */
+  transitions: Record<string, TransitionTarget<S, C>>
+  /**
+   * The starting state; defaults to the first declared state.
+   */
+  initial?: S
+  caseSensitive?: boolean
+  /**
+   * Passed to `when` guards, function targets and entry/exit actions.
+   */
+  context?: C
+}
+
+export type MachineErrorCode = 'invalid-config' | 'unknown-state' | 'invalid-transition'
+
+export type MachineError = Error & {
+  name: 'MachineError'
+  code: MachineErrorCode
+  /**
+   * The state the machine was in, for errors raised by `send`.
+   */
+  state?: string
+  event?: MatchValue
+  /**
+   * The offending target state, for 'unknown-state' errors.
+   */
+  target?: unknown
 }
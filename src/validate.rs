@@ -0,0 +1,563 @@
+use crate::cron::{cron_matches, is_cron_key};
+use crate::date::{date_matches, DatePattern};
+use crate::fuzzy::{fuzzy_matches, is_fuzzy_key};
+use crate::phonetic::{is_phonetic_key, phonetic_matches};
+use crate::{
+  decode_value, describe_value, effective_regex_flags, exact_arm, exact_keys, get_handler,
+  get_predicate_function, get_string_value, member_matches, parse_range_key, parse_regex_key,
+  parse_text_key, range_matches, regex_test, text_matches, try_compile_regex, type_matches,
+  wildcard_test, wildcard_to_regex, ArmKind, MatchOptions, PatternGroups, DEFAULT_HANDLER,
+  NONE_VALUE, PREFIX_ANY, PREFIX_LIT, PREFIX_NOT, PREFIX_REGEX, PREFIX_TEXT, PREFIX_TYPE,
+  PREFIX_WHEN, SOME_VALUE, TYPE_NAMES,
+};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone, Copy)]
+enum Severity {
+  Error,
+  Warning,
+}
+
+impl Severity {
+  fn as_str(self) -> &'static str {
+    match self {
+      Severity::Error => "error",
+      Severity::Warning => "warning",
+    }
+  }
+}
+
+struct Diagnostic {
+  severity: Severity,
+  code: &'static str,
+  key: String,
+  message: String,
+}
+
+impl Diagnostic {
+  fn error(code: &'static str, key: &str, message: String) -> Self {
+    Self {
+      severity: Severity::Error,
+      code,
+      key: key.to_string(),
+      message,
+    }
+  }
+
+  fn warning(code: &'static str, key: &str, message: String) -> Self {
+    Self {
+      severity: Severity::Warning,
+      code,
+      key: key.to_string(),
+      message,
+    }
+  }
+
+  fn to_js(&self) -> Object {
+    let obj = Object::new();
+    let _ = Reflect::set(
+      &obj,
+      &JsValue::from_str("severity"),
+      &JsValue::from_str(self.severity.as_str()),
+    );
+    let _ = Reflect::set(
+      &obj,
+      &JsValue::from_str("code"),
+      &JsValue::from_str(self.code),
+    );
+    let _ = Reflect::set(
+      &obj,
+      &JsValue::from_str("key"),
+      &JsValue::from_str(&self.key),
+    );
+    let _ = Reflect::set(
+      &obj,
+      &JsValue::from_str("message"),
+      &JsValue::from_str(&self.message),
+    );
+    obj
+  }
+}
+
+struct Members {
+  values: Vec<(String, JsValue)>,
+  predicates: Vec<String>,
+}
+
+fn decode_members(key: &str, prefix: &str) -> Result<Members, String> {
+  let values_part = &key[prefix.len()..];
+  if values_part.is_empty() {
+    return Err("no values are encoded in the key".to_string());
+  }
+
+  let mut members = Members {
+    values: Vec::new(),
+    predicates: Vec::new(),
+  };
+  for encoded in values_part.split('|') {
+    if encoded.starts_with(PREFIX_TEXT) {
+      if parse_text_key(encoded).is_none() {
+        return Err(format!("cannot parse string predicate {:?}", encoded));
+      }
+      members.predicates.push(encoded.to_string());
+    } else {
+      match decode_value(encoded) {
+        Some(value) => members.values.push((encoded.to_string(), value)),
+        None => return Err(format!("cannot decode value {:?}", encoded)),
+      }
+    }
+  }
+  Ok(members)
+}
+
+struct Analysis<'a> {
+  patterns: &'a Object,
+  groups: &'a PatternGroups,
+  options: MatchOptions,
+  diagnostics: Vec<Diagnostic>,
+  shadowed_by: Option<String>,
+  consumed: Vec<(String, String)>,
+  remaining: Option<Vec<JsValue>>,
+  narrowed_by: String,
+}
+
+impl Analysis<'_> {
+  fn is_shadowed(&mut self, key: &str) -> bool {
+    if let Some(by) = &self.shadowed_by {
+      let message = format!(
+        "`{}` can never match because {} always matches first",
+        key, by
+      );
+      self
+        .diagnostics
+        .push(Diagnostic::warning("shadowed", key, message));
+      true
+    } else {
+      false
+    }
+  }
+
+  fn reaches(&self, value: &JsValue) -> bool {
+    let has_exact_arm = exact_keys(value, &self.options)
+      .into_iter()
+      .any(|(kind, key)| {
+        exact_arm(self.patterns, || self.groups, kind, key, &self.options).is_some()
+      });
+    !has_exact_arm
+      && !self
+        .consumed
+        .iter()
+        .any(|(member, _)| member_matches(member, value, &self.options))
+  }
+
+  fn report_unreachable(&mut self, key: &str) {
+    let message = format!(
+      "`{}` can never match: every value that gets past `{}` is handled by earlier arms",
+      key, self.narrowed_by
+    );
+    self
+      .diagnostics
+      .push(Diagnostic::warning("unreachable", key, message));
+  }
+
+  fn when_arm(&mut self, key: &str, handler: &JsValue) {
+    let condition = &key[PREFIX_WHEN.len()..];
+    match condition {
+      "true" => {
+        if !self.is_shadowed(key) && self.options.accepts(handler) {
+          self.shadowed_by = Some(format!("`{}`", key));
+        }
+      }
+      "false" => {
+        let message = format!("`{}` is created from `when(false)` and never matches", key);
+        self
+          .diagnostics
+          .push(Diagnostic::warning("never-matches", key, message));
+      }
+      hash => match get_predicate_function(hash) {
+        Ok(Some(_)) => {
+          self.is_shadowed(key);
+        }
+        _ => {
+          let message = format!(
+            "`{}` does not refer to a registered predicate; create it with `when()`",
+            key
+          );
+          self
+            .diagnostics
+            .push(Diagnostic::error("malformed-key", key, message));
+        }
+      },
+    }
+  }
+
+  fn any_arm(&mut self, key: &str, handler: &JsValue) {
+    let members = match decode_members(key, PREFIX_ANY) {
+      Ok(members) => members,
+      Err(reason) => {
+        let message = format!("`{}` is not a valid `any()` key: {}", key, reason);
+        self
+          .diagnostics
+          .push(Diagnostic::error("malformed-key", key, message));
+        return;
+      }
+    };
+    if !self.options.accepts(handler) || self.is_shadowed(key) {
+      return;
+    }
+
+    let mut reachable = !members.predicates.is_empty();
+    for (encoded, value) in members.values {
+      let duplicate_of = self
+        .consumed
+        .iter()
+        .find(|(prev, _)| member_matches(prev, &value, &self.options))
+        .map(|(_, prev_key)| prev_key.clone());
+      match duplicate_of {
+        Some(prev_key) => {
+          let message = format!(
+            "{} is already matched by `{}`",
+            describe_value(&value),
+            prev_key
+          );
+          self
+            .diagnostics
+            .push(Diagnostic::warning("duplicate-value", key, message));
+        }
+        None => {
+          reachable = true;
+          self.consumed.push((encoded, key.to_string()));
+        }
+      }
+    }
+    for predicate in members.predicates {
+      self.consumed.push((predicate, key.to_string()));
+    }
+
+    if !reachable {
+      let message = format!(
+        "`{}` can never match: all of its values are matched by earlier arms",
+        key
+      );
+      self
+        .diagnostics
+        .push(Diagnostic::warning("unreachable", key, message));
+    }
+  }
+
+  fn not_arm(&mut self, key: &str, handler: &JsValue) {
+    let members = match decode_members(key, PREFIX_NOT) {
+      Ok(members) => members,
+      Err(reason) => {
+        let message = format!("`{}` is not a valid `not()` key: {}", key, reason);
+        self
+          .diagnostics
+          .push(Diagnostic::error("malformed-key", key, message));
+        return;
+      }
+    };
+    if !self.options.accepts(handler) || self.is_shadowed(key) {
+      return;
+    }
+    // Coerced or normalized spellings of the excluded values, such as `' 42'`,
+    // get past too.
+    if self.options.coerce || self.options.normalize.enabled() {
+      self.remaining = None;
+      return;
+    }
+
+    let options = self.options.clone();
+    let excluded = |value: &JsValue| {
+      members
+        .values
+        .iter()
+        .map(|(encoded, _)| encoded)
+        .chain(&members.predicates)
+        .any(|member| member_matches(member, value, &options))
+    };
+    let remaining = match self.remaining.take() {
+      Some(remaining) => {
+        if remaining.iter().all(excluded) {
+          self.report_unreachable(key);
+        }
+        remaining.into_iter().filter(excluded).collect()
+      }
+      None if !members.predicates.is_empty() => return,
+      None => {
+        let mut remaining = Vec::with_capacity(members.values.len());
+        for (_, value) in &members.values {
+          if self.reaches(value) {
+            remaining.push(value.clone());
+          }
+        }
+        self.narrowed_by = key.to_string();
+        remaining
+      }
+    };
+    self.remaining = Some(remaining);
+  }
+
+  fn filter_arm(&mut self, key: &str, handler: &JsValue, test: impl Fn(&JsValue) -> bool) {
+    if !self.options.accepts(handler) || self.is_shadowed(key) {
+      return;
+    }
+    if let Some(remaining) = self.remaining.take() {
+      if !remaining.iter().any(&test) {
+        self.report_unreachable(key);
+      }
+      self.remaining = Some(remaining.into_iter().filter(|v| !test(v)).collect());
+    }
+  }
+
+  fn regex_arm(&mut self, key: &str, handler: &JsValue) {
+    let payload = &key[PREFIX_REGEX.len()..];
+    let parsed = if payload.contains("::") {
+      parse_regex_key(key)
+    } else {
+      None
+    };
+    let Some((pattern, flags)) = parsed else {
+      let message = format!(
+        "`{}` is not a valid `regex()` key: missing flags separator",
+        key
+      );
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    };
+
+    let effective_flags = effective_regex_flags(flags, self.options.case_sensitive);
+    let regex = match try_compile_regex(&pattern, &effective_flags) {
+      Ok(regex) => regex,
+      Err(err) => {
+        let reason = err
+          .dyn_ref::<js_sys::Error>()
+          .map(|e| String::from(e.message()))
+          .unwrap_or_else(|| get_string_value(&err));
+        let message = format!("`{}` does not compile: {}", key, reason);
+        self
+          .diagnostics
+          .push(Diagnostic::error("invalid-regex", key, message));
+        return;
+      }
+    };
+
+    let options = self.options.clone();
+    self.filter_arm(key, handler, |value| {
+      regex_test(&regex, &get_string_value(value), &options)
+    });
+  }
+
+  fn wildcard_arm(&mut self, key: &str, handler: &JsValue) {
+    let regex = wildcard_to_regex(key, &self.options);
+    let options = self.options.clone();
+    self.filter_arm(key, handler, |value| wildcard_test(&regex, value, &options));
+  }
+
+  fn fuzzy_arm(&mut self, key: &str, handler: &JsValue) {
+    if !is_fuzzy_key(key) {
+      let message = format!("`{}` is not a valid `fuzzy()` key", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    let options = self.options.clone();
+    self.filter_arm(key, handler, |value| fuzzy_matches(key, value, &options));
+  }
+
+  fn phonetic_arm(&mut self, key: &str, handler: &JsValue) {
+    if !is_phonetic_key(key) {
+      let message = format!("`{}` is not a valid `soundsLike()` key", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    self.filter_arm(key, handler, |value| phonetic_matches(key, value));
+  }
+
+  fn range_arm(&mut self, key: &str, handler: &JsValue) {
+    if parse_range_key(key).is_none() {
+      let message = format!("`{}` is not a valid `range()` key", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    let options = self.options.clone();
+    self.filter_arm(key, handler, |value| range_matches(key, value, &options));
+  }
+
+  fn date_arm(&mut self, key: &str, handler: &JsValue) {
+    if DatePattern::parse(key).is_none() {
+      let message = format!("`{}` is not a valid date pattern key", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    self.filter_arm(key, handler, |value| date_matches(key, value));
+  }
+
+  fn cron_arm(&mut self, key: &str, handler: &JsValue) {
+    if !is_cron_key(key) {
+      let message = format!("`{}` is not a valid `cron()` key", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    self.filter_arm(key, handler, |value| cron_matches(key, value));
+  }
+
+  fn text_arm(&mut self, key: &str, handler: &JsValue) {
+    if parse_text_key(key).is_none() {
+      let message = format!("`{}` is not a valid string predicate key", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    let options = self.options.clone();
+    self.filter_arm(key, handler, |value| text_matches(key, value, &options));
+  }
+
+  fn type_arm(&mut self, key: &str, handler: &JsValue) {
+    let name = &key[PREFIX_TYPE.len()..];
+    if !TYPE_NAMES.contains(&name) && !["integer", "finite", "nan"].contains(&name) {
+      let message = format!("`{}` does not name a type known to `type()`", key);
+      self
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", key, message));
+      return;
+    }
+    self.filter_arm(key, handler, |value| type_matches(key, value));
+  }
+
+  fn default_arm(&mut self) {
+    if get_handler(self.patterns, DEFAULT_HANDLER, &self.options).is_none()
+      || self.is_shadowed(DEFAULT_HANDLER)
+    {
+      return;
+    }
+    if self.remaining.as_ref().is_some_and(|r| r.is_empty()) {
+      self.report_unreachable(DEFAULT_HANDLER);
+    }
+  }
+}
+
+#[wasm_bindgen(js_name = "validatePatterns")]
+pub fn validate_patterns(patterns: &Object, options: Option<Object>) -> Result<Array, JsValue> {
+  let options = MatchOptions::from_js(options.as_ref())?;
+  let constants = options.constants;
+  let groups = PatternGroups::from_object(patterns, &options);
+  let mut analysis = Analysis {
+    patterns,
+    groups: &groups,
+    options,
+    diagnostics: Vec::new(),
+    shadowed_by: None,
+    consumed: Vec::new(),
+    remaining: None,
+    narrowed_by: String::new(),
+  };
+
+  let keys = Object::keys(patterns);
+  for key in keys.iter() {
+    let Some(key_str) = key.as_string() else {
+      continue;
+    };
+    if let Ok(handler) = Reflect::get(patterns, &key) {
+      if !constants && !handler.is_function() {
+        let message = format!(
+          "handler for `{}` is not a function and is never called unless `constants` is enabled",
+          key_str
+        );
+        analysis
+          .diagnostics
+          .push(Diagnostic::error("non-function-handler", &key_str, message));
+      }
+    }
+  }
+
+  for key in keys.iter().filter_map(|key| key.as_string()) {
+    if ArmKind::of(&key) == ArmKind::Literal && decode_value(&key[PREFIX_LIT.len()..]).is_none() {
+      let message = format!("`{}` is not a valid `lit()` key", key);
+      analysis
+        .diagnostics
+        .push(Diagnostic::error("malformed-key", &key, message));
+    }
+  }
+
+  if analysis.options.canonicalizes() {
+    for key in keys.iter().filter_map(|key| key.as_string()) {
+      if !matches!(ArmKind::of(&key), ArmKind::Exact | ArmKind::Literal) {
+        continue;
+      }
+      let colliding = groups.folded_keys(&analysis.options.canonical_key(&key));
+      if let Some(first) = colliding.first().filter(|first| **first != key) {
+        let message = format!(
+          "`{}` only differs from `{}` by case, normalization or coercion, so they collide when matching",
+          key, first
+        );
+        analysis
+          .diagnostics
+          .push(Diagnostic::error("case-collision", &key, message));
+      }
+    }
+  }
+
+  let has_handler = |key: &str| get_handler(patterns, key, &analysis.options).is_some();
+  if has_handler(SOME_VALUE) && has_handler(NONE_VALUE) {
+    analysis.shadowed_by = Some(format!("`{}` and `{}`", SOME_VALUE, NONE_VALUE));
+  }
+
+  for (key, handler) in &groups.when {
+    analysis.when_arm(key, handler);
+  }
+  for (key, handler) in &groups.any {
+    analysis.any_arm(key, handler);
+  }
+  for (key, handler) in &groups.not {
+    analysis.not_arm(key, handler);
+  }
+  for (key, handler) in &groups.range {
+    analysis.range_arm(key, handler);
+  }
+  for (key, handler) in &groups.dates {
+    analysis.date_arm(key, handler);
+  }
+  for (key, handler) in &groups.cron {
+    analysis.cron_arm(key, handler);
+  }
+  for (key, handler) in &groups.text {
+    analysis.text_arm(key, handler);
+  }
+  for (key, handler) in &groups.regex {
+    analysis.regex_arm(key, handler);
+  }
+  for (key, handler) in &groups.wildcard {
+    analysis.wildcard_arm(key, handler);
+  }
+  for (key, handler) in &groups.fuzzy {
+    analysis.fuzzy_arm(key, handler);
+  }
+  for (key, handler) in &groups.phonetic {
+    analysis.phonetic_arm(key, handler);
+  }
+  for (key, handler) in &groups.types {
+    analysis.type_arm(key, handler);
+  }
+  analysis.default_arm();
+
+  Ok(
+    analysis
+      .diagnostics
+      .iter()
+      .map(|diagnostic| JsValue::from(diagnostic.to_js()))
+      .collect(),
+  )
+}
@@ -1,15 +1,115 @@
-import type { MatchValue, Options, PatternHandler, PatternMap } from '../types'
+import type {
+  AsyncOptions,
+  AsyncPatternMap,
+  BestMatch,
+  BestMatchOptions,
+  ConstantPatternMap,
+  ConstantTuplePatternMap,
+  Diagnostic,
+  ExhaustivenessReport,
+  MatchedPattern,
+  MatchValue,
+  Options,
+  PatternHandler,
+  PatternMap,
+  PhoneticAlgorithm,
+  TimeZone,
+  TuplePatternMap,
+  TypeName,
+} from '../types'
 
 export declare function some(): string
 export declare function none(): string
-export declare function when<T>(condition: ((value: T) => boolean) | boolean): string
+export declare function when<T, C = any>(
+  condition: ((value: T, context: C) => boolean | PromiseLike<boolean>) | boolean,
+): string
 export declare function any(args: MatchValue[]): string
 export declare function not(args: MatchValue[]): string
+export declare function range(min: number | bigint, max: number | bigint): string
+export declare function before(date: Date | number): string
+export declare function after(date: Date | number): string
+export declare function between(start: Date | number, end: Date | number): string
+export declare function weekday(days: number | number[], timeZone?: TimeZone): string
+export declare function hour(from: number, to?: number, timeZone?: TimeZone): string
+export declare function cron(expression: string, timeZone?: TimeZone): string
 export declare function regex(pattern: string, flags?: string): string
-export declare function match<R>(value: MatchValue, patterns: PatternMap<R>, options?: Options): R
+export declare function fuzzy(target: string, maxDistance?: number): string
+export declare function soundsLike(name: string, algorithm?: PhoneticAlgorithm): string
+export declare function lit(value: MatchValue): string
+export declare function startsWith(prefix: string): string
+export declare function endsWith(suffix: string): string
+export declare function includes(substring: string): string
+export declare function minLength(length: number): string
+export declare function maxLength(length: number): string
+export declare function length(length: number): string
+export declare function type(name: TypeName): string
+export declare function integer(): string
+export declare function finite(): string
+export declare function nan(): string
+export declare function tuple(positions: MatchValue[]): string
+export declare class Machine {
+  private constructor()
+  free(): void
+  readonly state: string
+  can(event: MatchValue): boolean
+  send(event: MatchValue): string
+}
+export declare function createMachine(config: object): Machine
+export declare class Router<R = any, C = any> {
+  constructor(options?: Options)
+  free(): void
+  on(pattern: string, handler: PatternHandler<R, C>): number
+  off(id: number): boolean
+  dispatch(value: MatchValue, context?: C): R[]
+}
+export declare function matchTuple<R>(
+  values: readonly MatchValue[],
+  patterns: TuplePatternMap<R> | ConstantTuplePatternMap<R>,
+  options?: Options,
+): R
+export declare function match<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): R
+export declare function matchAsync<R>(
+  value: MatchValue,
+  patterns: AsyncPatternMap<R>,
+  options?: AsyncOptions,
+): Promise<R>
+export declare function matchAll<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): R[]
+export declare function bestMatch<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: BestMatchOptions,
+): BestMatch<R>
+export declare function which<R>(
+  value: MatchValue,
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): MatchedPattern | undefined
 export declare function ifLet<R>(
   value: MatchValue,
   pattern: MatchValue,
   handler: PatternHandler<R>,
 ): R | undefined
 export declare function matches(value: MatchValue, pattern: MatchValue, options?: Options): boolean
+export declare function validatePatterns<R>(
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): Diagnostic[]
+export declare function checkExhaustive<R>(
+  domain: readonly MatchValue[],
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): ExhaustivenessReport
+export declare function matchExhaustive<R>(
+  value: MatchValue,
+  domain: readonly MatchValue[],
+  patterns: PatternMap<R> | ConstantPatternMap<R>,
+  options?: Options,
+): R
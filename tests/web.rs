@@ -151,8 +151,8 @@ fn test_iflet_and_matches() {
   let pat2 = JsValue::from_str("def");
   let result2 = if_let(&v, &pat2, &handler);
   assert!(result2.is_undefined());
-  assert!(matches(&v, &pat, None));
-  assert!(!matches(&v, &pat2, None));
+  assert!(matches(&v, &pat, None).unwrap());
+  assert!(!matches(&v, &pat2, None).unwrap());
 }
 
 #[wasm_bindgen_test]
@@ -730,3 +730,2075 @@ fn test_when_pattern_with_direct_boolean() {
     "default"
   );
 }
+
+fn diagnostic_codes(diagnostics: &Array) -> Vec<(String, String)> {
+  diagnostics
+    .iter()
+    .map(|d| {
+      let code = Reflect::get(&d, &JsValue::from_str("code")).unwrap();
+      let key = Reflect::get(&d, &JsValue::from_str("key")).unwrap();
+      (code.as_string().unwrap(), key.as_string().unwrap())
+    })
+    .collect()
+}
+
+#[wasm_bindgen_test]
+fn test_validate_patterns_clean() {
+  let patterns = Object::new();
+  let arr = Array::new();
+  arr.push(&JsValue::from(1));
+  let any_pat = any(&arr).unwrap();
+  let regex_pat = regex("^foo", None).unwrap();
+  let f = Function::new_no_args("return 1;");
+  Reflect::set(&patterns, &JsValue::from_str("foo"), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&any_pat), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&regex_pat), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &f).unwrap();
+  assert_eq!(validate_patterns(&patterns, None).unwrap().length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_validate_patterns_errors() {
+  let patterns = Object::new();
+  let f = Function::new_no_args("return 1;");
+  let bad_regex = regex("(foo", None).unwrap();
+  let bad_flags = regex("foo", Some("zz".to_string())).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&bad_regex), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&bad_flags), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("any::oops"), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("when::123"), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("bar"), &JsValue::from(1)).unwrap();
+  let codes = diagnostic_codes(&validate_patterns(&patterns, None).unwrap());
+  assert!(codes.contains(&("invalid-regex".to_string(), bad_regex)));
+  assert!(codes.contains(&("invalid-regex".to_string(), bad_flags)));
+  assert!(codes.contains(&("malformed-key".to_string(), "any::oops".to_string())));
+  assert!(codes.contains(&("malformed-key".to_string(), "when::123".to_string())));
+  assert!(codes.contains(&("non-function-handler".to_string(), "bar".to_string())));
+}
+
+#[wasm_bindgen_test]
+fn test_validate_patterns_unreachable_arms() {
+  let f = Function::new_no_args("return 1;");
+  let ab = Array::new();
+  ab.push(&JsValue::from_str("a"));
+  ab.push(&JsValue::from_str("b"));
+  let b = Array::new();
+  b.push(&JsValue::from_str("b"));
+  let c = Array::new();
+  c.push(&JsValue::from_str("c"));
+  let any_ab = any(&ab).unwrap();
+  let any_b = any(&b).unwrap();
+  let not_c = not(&c).unwrap();
+  let regex_x = regex("^x", None).unwrap();
+
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str(&any_ab), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&any_b), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&not_c), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&regex_x), &f).unwrap();
+  let codes = diagnostic_codes(&validate_patterns(&patterns, None).unwrap());
+  assert!(codes.contains(&("duplicate-value".to_string(), any_b.clone())));
+  assert!(codes.contains(&("unreachable".to_string(), any_b)));
+  assert!(codes.contains(&("unreachable".to_string(), regex_x)));
+
+  let when_true = when(&JsValue::from(true)).unwrap();
+  let shadowed = Object::new();
+  Reflect::set(&shadowed, &JsValue::from_str(&when_true), &f).unwrap();
+  Reflect::set(&shadowed, &JsValue::from_str(&not_c), &f).unwrap();
+  Reflect::set(&shadowed, &JsValue::from_str("_"), &f).unwrap();
+  let codes = diagnostic_codes(&validate_patterns(&shadowed, None).unwrap());
+  assert!(codes.contains(&("shadowed".to_string(), not_c)));
+  assert!(codes.contains(&("shadowed".to_string(), "_".to_string())));
+}
+
+#[wasm_bindgen_test]
+fn test_check_exhaustive() {
+  let domain = Array::new();
+  domain.push(&JsValue::from_str("red"));
+  domain.push(&JsValue::from_str("green"));
+  domain.push(&JsValue::from_str("blue"));
+  let rg = Array::new();
+  rg.push(&JsValue::from_str("red"));
+  rg.push(&JsValue::from_str("green"));
+  let any_rg = any(&rg).unwrap();
+  let f = Function::new_no_args("return 1;");
+
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str(&any_rg), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("yellow"), &f).unwrap();
+  let report = check_exhaustive(&domain, &patterns, None).unwrap();
+  let exhaustive = Reflect::get(&report, &JsValue::from_str("exhaustive")).unwrap();
+  let uncovered: Array = Reflect::get(&report, &JsValue::from_str("uncovered"))
+    .unwrap()
+    .into();
+  let unreachable: Array = Reflect::get(&report, &JsValue::from_str("unreachable"))
+    .unwrap()
+    .into();
+  assert_eq!(exhaustive.as_bool(), Some(false));
+  assert_eq!(uncovered.length(), 1);
+  assert_eq!(uncovered.get(0).as_string().unwrap(), "blue");
+  assert_eq!(unreachable.length(), 1);
+  assert_eq!(unreachable.get(0).as_string().unwrap(), "yellow");
+
+  Reflect::set(&patterns, &JsValue::from_str("b*"), &f).unwrap();
+  let report = check_exhaustive(&domain, &patterns, None).unwrap();
+  let exhaustive = Reflect::get(&report, &JsValue::from_str("exhaustive")).unwrap();
+  assert_eq!(exhaustive.as_bool(), Some(true));
+}
+
+#[wasm_bindgen_test]
+fn test_match_exhaustive() {
+  let domain = Array::new();
+  domain.push(&JsValue::from(1));
+  domain.push(&JsValue::from(2));
+  let f_one = Function::new_no_args("return 'one';");
+  let f_rest = Function::new_no_args("return 'rest';");
+  let one = Array::new();
+  one.push(&JsValue::from(1));
+  let not_one = not(&one).unwrap();
+
+  let partial = Object::new();
+  Reflect::set(&partial, &JsValue::from_str("1"), &f_one).unwrap();
+  let err = match_exhaustive(&JsValue::from(1), &domain, &partial, None).unwrap_err();
+  assert!(err.as_string().unwrap().contains("Non-exhaustive"));
+
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str("1"), &f_one).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&not_one), &f_rest).unwrap();
+  assert_eq!(
+    match_exhaustive(&JsValue::from(2), &domain, &patterns, None)
+      .unwrap()
+      .as_string()
+      .unwrap(),
+    "rest"
+  );
+  let err = match_exhaustive(&JsValue::from(3), &domain, &patterns, None).unwrap_err();
+  assert!(err.as_string().unwrap().contains("not a member"));
+}
+
+#[wasm_bindgen_test]
+fn test_match_all() {
+  let ab = Array::new();
+  ab.push(&JsValue::from_str("apple"));
+  ab.push(&JsValue::from_str("banana"));
+  let any_fruit = any(&ab).unwrap();
+  let regex_a = regex("^a", None).unwrap();
+  let patterns = Object::new();
+  let f_exact = Function::new_no_args("return 'exact';");
+  let f_any = Function::new_no_args("return 'fruit';");
+  let f_regex = Function::new_no_args("return 'starts-with-a';");
+  let f_wild = Function::new_no_args("return 'wild';");
+  let f_def = Function::new_no_args("return 'def';");
+  Reflect::set(&patterns, &JsValue::from_str("*le"), &f_wild).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&regex_a), &f_regex).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&any_fruit), &f_any).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("apple"), &f_exact).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &f_def).unwrap();
+
+  let results = match_all(&JsValue::from_str("apple"), &patterns, None).unwrap();
+  let results: Vec<String> = results.iter().map(|r| r.as_string().unwrap()).collect();
+  assert_eq!(results, vec!["exact", "fruit", "starts-with-a", "wild"]);
+
+  let results = match_all(&JsValue::from_str("cherry"), &patterns, None).unwrap();
+  assert_eq!(results.length(), 1);
+  assert_eq!(results.get(0).as_string().unwrap(), "def");
+
+  let no_default = Object::new();
+  Reflect::set(&no_default, &JsValue::from_str("apple"), &f_exact).unwrap();
+  let results = match_all(&JsValue::from_str("cherry"), &no_default, None).unwrap();
+  assert_eq!(results.length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_which() {
+  let arr = Array::new();
+  arr.push(&JsValue::from(1));
+  let any_pat = any(&arr).unwrap();
+  let patterns = Object::new();
+  let f = Function::new_no_args("throw new Error('handlers must not run');");
+  Reflect::set(&patterns, &JsValue::from_str("foo"), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&any_pat), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("ba?"), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &f).unwrap();
+
+  let kind_and_key = |value: JsValue| {
+    let arm = which(&value, &patterns, None).unwrap();
+    let kind = Reflect::get(&arm, &JsValue::from_str("kind")).unwrap();
+    let key = Reflect::get(&arm, &JsValue::from_str("key")).unwrap();
+    (kind.as_string().unwrap(), key.as_string().unwrap())
+  };
+  assert_eq!(
+    kind_and_key(JsValue::from_str("foo")),
+    ("exact".to_string(), "foo".to_string())
+  );
+  assert_eq!(
+    kind_and_key(JsValue::from(1)),
+    ("any".to_string(), any_pat.clone())
+  );
+  assert_eq!(
+    kind_and_key(JsValue::from_str("bar")),
+    ("wildcard".to_string(), "ba?".to_string())
+  );
+  assert!(which(&JsValue::from_str("zzz"), &patterns, None)
+    .unwrap()
+    .is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn test_matches_pattern_kinds() {
+  let arr = Array::new();
+  arr.push(&JsValue::from_str("a"));
+  arr.push(&JsValue::from_str("b"));
+  let any_pat = JsValue::from_str(&any(&arr).unwrap());
+  let not_pat = JsValue::from_str(&not(&arr).unwrap());
+  let v = JsValue::from_str("a");
+  assert!(matches(&v, &any_pat, None).unwrap());
+  assert!(!matches(&v, &not_pat, None).unwrap());
+  assert!(matches(&v, &JsValue::from_str("__SOME__"), None).unwrap());
+  assert!(!matches(&v, &JsValue::from_str("__NONE__"), None).unwrap());
+  assert!(matches(&JsValue::from_str("abc"), &JsValue::from_str("a*"), None).unwrap());
+  let handler = Function::new_no_args("return 'hit';");
+  assert_eq!(
+    if_let(&JsValue::from_str("b"), &any_pat, &handler)
+      .as_string()
+      .unwrap(),
+    "hit"
+  );
+  assert!(if_let(
+    &JsValue::from_str("toString"),
+    &JsValue::from_str("x"),
+    &handler
+  )
+  .is_undefined());
+  // `_` is only a fallback inside a pattern map, never a pattern on its own.
+  assert!(!matches(&v, &JsValue::from_str("_"), None).unwrap());
+  assert!(!matches(&JsValue::from_str("_"), &JsValue::from_str("_"), None).unwrap());
+  assert!(if_let(&v, &JsValue::from_str("_"), &handler).is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn test_constant_handlers() {
+  let arr = Array::new();
+  arr.push(&JsValue::from(2));
+  arr.push(&JsValue::from(3));
+  let any_pat = any(&arr).unwrap();
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str("a"), &JsValue::from(1)).unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&any_pat),
+    &JsValue::from_str("small"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("b*"),
+    &JsValue::from_bool(false),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("c"),
+    &Function::new_no_args("return 'called';"),
+  )
+  .unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &JsValue::from(0)).unwrap();
+
+  let options = Object::new();
+  Reflect::set(
+    &options,
+    &JsValue::from_str("constants"),
+    &JsValue::from_bool(true),
+  )
+  .unwrap();
+  let run = |value: JsValue| match_pattern(&value, &patterns, Some(options.clone())).unwrap();
+  assert_eq!(run(JsValue::from_str("a")).as_f64(), Some(1.0));
+  assert_eq!(run(JsValue::from(3)).as_string().unwrap(), "small");
+  assert_eq!(run(JsValue::from_str("bee")).as_bool(), Some(false));
+  assert_eq!(run(JsValue::from_str("c")).as_string().unwrap(), "called");
+  assert_eq!(run(JsValue::from_str("zzz")).as_f64(), Some(0.0));
+  assert_eq!(run(JsValue::from_str("toString")).as_f64(), Some(0.0));
+
+  let err = match_pattern(&JsValue::from_str("a"), &patterns, None).unwrap_err();
+  assert!(err.as_string().unwrap().contains("No pattern matched"));
+}
+
+#[wasm_bindgen_test]
+fn test_missing_options_use_defaults() {
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("hello"),
+    &Function::new_no_args("return 'exact';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'default';"),
+  )
+  .unwrap();
+  let result = |options: Object| {
+    match_pattern(&JsValue::from_str("HELLO"), &patterns, Some(options))
+      .unwrap()
+      .as_string()
+      .unwrap()
+  };
+
+  // An options object without `caseSensitive` matches case-insensitively.
+  assert_eq!(result(Object::new()), "exact");
+  let with_context = Object::new();
+  Reflect::set(
+    &with_context,
+    &JsValue::from_str("context"),
+    &JsValue::from(1),
+  )
+  .unwrap();
+  assert_eq!(result(with_context), "exact");
+  let explicit_undefined = Object::new();
+  Reflect::set(
+    &explicit_undefined,
+    &JsValue::from_str("caseSensitive"),
+    &JsValue::UNDEFINED,
+  )
+  .unwrap();
+  assert_eq!(result(explicit_undefined), "exact");
+
+  let sensitive = Object::new();
+  Reflect::set(
+    &sensitive,
+    &JsValue::from_str("caseSensitive"),
+    &JsValue::TRUE,
+  )
+  .unwrap();
+  assert_eq!(result(sensitive), "default");
+  assert_eq!(
+    match_pattern(&JsValue::from_str("HELLO"), &patterns, None)
+      .unwrap()
+      .as_string()
+      .unwrap(),
+    "default"
+  );
+  assert!(matches(
+    &JsValue::from_str("HELLO"),
+    &JsValue::from_str("hello"),
+    Some(Object::new())
+  )
+  .unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn test_match_async_predicates() {
+  let is_big = Function::new_no_args("return Promise.resolve(arguments[0] > 10);");
+  let is_odd = Function::new_no_args("return arguments[0] % 2 === 1;");
+  let rejects = Function::new_no_args("return Promise.reject(new Error('offline'));");
+  let when_big = when(&is_big).unwrap();
+  let when_odd = when(&is_odd).unwrap();
+  let when_rejects = when(&rejects).unwrap();
+
+  let patterns = Object::new();
+  let f_rejects = Function::new_no_args("return 'rejected';");
+  let f_big = Function::new_no_args("return Promise.resolve('big');");
+  let f_odd = Function::new_no_args("return 'odd';");
+  let f_def = Function::new_no_args("return 'def';");
+  Reflect::set(&patterns, &JsValue::from_str(&when_rejects), &f_rejects).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&when_big), &f_big).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&when_odd), &f_odd).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &f_def).unwrap();
+
+  let result = match_async(JsValue::from(12), patterns.clone(), None)
+    .await
+    .unwrap();
+  assert_eq!(result.as_string().unwrap(), "big");
+  let result = match_async(JsValue::from(13), patterns.clone(), None)
+    .await
+    .unwrap();
+  assert_eq!(result.as_string().unwrap(), "big");
+  let result = match_async(JsValue::from(3), patterns.clone(), None)
+    .await
+    .unwrap();
+  assert_eq!(result.as_string().unwrap(), "odd");
+  let result = match_async(JsValue::from(4), patterns.clone(), None)
+    .await
+    .unwrap();
+  assert_eq!(result.as_string().unwrap(), "def");
+
+  let sync_result = match_pattern(&JsValue::from(12), &patterns, None).unwrap();
+  assert_eq!(sync_result.as_string().unwrap(), "def");
+}
+
+#[wasm_bindgen_test]
+async fn test_match_async_no_match() {
+  let patterns = Object::new();
+  let err = match_async(JsValue::from_str("x"), patterns, None)
+    .await
+    .unwrap_err();
+  assert!(err.as_string().unwrap().contains("No pattern matched"));
+}
+
+fn async_options(entries: &[(&str, JsValue)]) -> Object {
+  let options = Object::new();
+  for (key, value) in entries {
+    Reflect::set(&options, &JsValue::from_str(key), value).unwrap();
+  }
+  options
+}
+
+#[wasm_bindgen_test]
+async fn test_match_async_timeout() {
+  let hangs = Function::new_no_args("return new Promise(() => {});");
+  let when_hangs = when(&hangs).unwrap();
+  let patterns = Object::new();
+  let f = Function::new_no_args("return 'never';");
+  Reflect::set(&patterns, &JsValue::from_str(&when_hangs), &f).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &f).unwrap();
+
+  let options = async_options(&[("timeout", JsValue::from(10))]);
+  let err = match_async(JsValue::from(1), patterns, Some(options))
+    .await
+    .unwrap_err();
+  let name = Reflect::get(&err, &JsValue::from_str("name")).unwrap();
+  let reason = Reflect::get(&err, &JsValue::from_str("reason")).unwrap();
+  let key = Reflect::get(&err, &JsValue::from_str("key")).unwrap();
+  assert_eq!(name.as_string().unwrap(), "MatchAbortError");
+  assert_eq!(reason.as_string().unwrap(), "timeout");
+  assert_eq!(key.as_string().unwrap(), when_hangs);
+}
+
+#[wasm_bindgen_test]
+async fn test_match_async_abort_signal() {
+  let controller_class = Reflect::get(&js_sys::global(), &JsValue::from_str("AbortController"))
+    .unwrap()
+    .unchecked_into::<Function>();
+  let controller = Reflect::construct(&controller_class, &Array::new()).unwrap();
+  let signal = Reflect::get(&controller, &JsValue::from_str("signal")).unwrap();
+  // Abort while the predicate is pending, so the error names it.
+  Function::new_with_args("controller", "setTimeout(() => controller.abort(), 10);")
+    .call1(&JsValue::NULL, &controller)
+    .unwrap();
+
+  let slow = Function::new_no_args("return new Promise((r) => setTimeout(() => r(true), 50));");
+  let when_slow = when(&slow).unwrap();
+  let patterns = Object::new();
+  let f = Function::new_no_args("return 'slow';");
+  Reflect::set(&patterns, &JsValue::from_str(&when_slow), &f).unwrap();
+
+  let options = async_options(&[("signal", signal)]);
+  let err = match_async(JsValue::from(1), patterns, Some(options))
+    .await
+    .unwrap_err();
+  let reason = Reflect::get(&err, &JsValue::from_str("reason")).unwrap();
+  let key = Reflect::get(&err, &JsValue::from_str("key")).unwrap();
+  assert_eq!(reason.as_string().unwrap(), "abort");
+  assert_eq!(key.as_string().unwrap(), when_slow);
+}
+
+#[wasm_bindgen_test]
+async fn test_match_async_pre_aborted_signal() {
+  let controller_class = Reflect::get(&js_sys::global(), &JsValue::from_str("AbortController"))
+    .unwrap()
+    .unchecked_into::<Function>();
+  let controller = Reflect::construct(&controller_class, &Array::new()).unwrap();
+  let signal = Reflect::get(&controller, &JsValue::from_str("signal")).unwrap();
+  let abort = Reflect::get(&controller, &JsValue::from_str("abort"))
+    .unwrap()
+    .unchecked_into::<Function>();
+  abort.call0(&controller).unwrap();
+
+  let calls = Array::new();
+  let handler = Function::new_with_args("value, context", "context.push(value); return 'ran';");
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str("a"), &handler).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str("_"), &handler).unwrap();
+
+  let options = async_options(&[("signal", signal), ("context", calls.clone().into())]);
+  let err = match_async(JsValue::from_str("a"), patterns, Some(options))
+    .await
+    .unwrap_err();
+  let reason = Reflect::get(&err, &JsValue::from_str("reason")).unwrap();
+  assert_eq!(reason.as_string().unwrap(), "abort");
+  assert!(Reflect::get(&err, &JsValue::from_str("key"))
+    .unwrap()
+    .is_undefined());
+  assert_eq!(calls.length(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn test_match_async_concurrent_priority() {
+  let slow_true =
+    Function::new_no_args("return new Promise((r) => setTimeout(() => r(true), 30));");
+  let slow_false =
+    Function::new_no_args("return new Promise((r) => setTimeout(() => r(false), 30));");
+  let fast_true = Function::new_no_args("return Promise.resolve(true);");
+  let when_slow_true = when(&slow_true).unwrap();
+  let when_slow_false = when(&slow_false).unwrap();
+  let when_fast_true = when(&fast_true).unwrap();
+  let f_slow = Function::new_no_args("return 'slow';");
+  let f_fast = Function::new_no_args("return 'fast';");
+  let options = || async_options(&[("predicates", JsValue::from_str("concurrent"))]);
+
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str(&when_slow_true), &f_slow).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&when_fast_true), &f_fast).unwrap();
+  let result = match_async(JsValue::from(1), patterns, Some(options()))
+    .await
+    .unwrap();
+  assert_eq!(result.as_string().unwrap(), "slow");
+
+  let patterns = Object::new();
+  Reflect::set(&patterns, &JsValue::from_str(&when_slow_false), &f_slow).unwrap();
+  Reflect::set(&patterns, &JsValue::from_str(&when_fast_true), &f_fast).unwrap();
+  let result = match_async(JsValue::from(1), patterns, Some(options()))
+    .await
+    .unwrap();
+  assert_eq!(result.as_string().unwrap(), "fast");
+}
+
+#[wasm_bindgen_test]
+fn test_match_context() {
+  let is_editor = Function::new_with_args("value, ctx", "return ctx.role === 'editor';");
+  let when_editor = when(&is_editor).unwrap();
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&when_editor),
+    &Function::new_with_args("value, ctx", "return value + ' by ' + ctx.name;"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'read only';"),
+  )
+  .unwrap();
+
+  let context = Object::new();
+  Reflect::set(
+    &context,
+    &JsValue::from_str("role"),
+    &JsValue::from_str("editor"),
+  )
+  .unwrap();
+  Reflect::set(
+    &context,
+    &JsValue::from_str("name"),
+    &JsValue::from_str("ada"),
+  )
+  .unwrap();
+  let options = Object::new();
+  Reflect::set(&options, &JsValue::from_str("context"), &context).unwrap();
+  let result = match_pattern(&JsValue::from_str("edit"), &patterns, Some(options)).unwrap();
+  assert_eq!(result.as_string().unwrap(), "edit by ada");
+
+  let context = Object::new();
+  Reflect::set(
+    &context,
+    &JsValue::from_str("role"),
+    &JsValue::from_str("viewer"),
+  )
+  .unwrap();
+  let options = Object::new();
+  Reflect::set(&options, &JsValue::from_str("context"), &context).unwrap();
+  let result = match_pattern(&JsValue::from_str("edit"), &patterns, Some(options)).unwrap();
+  assert_eq!(result.as_string().unwrap(), "read only");
+
+  let options = Object::new();
+  Reflect::set(&options, &JsValue::from_str("context"), &context).unwrap();
+  assert!(!matches(
+    &JsValue::from_str("edit"),
+    &JsValue::from_str(&when_editor),
+    Some(options)
+  )
+  .unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_match_tuple() {
+  let any_arr = Array::new();
+  any_arr.push(&JsValue::from_str("start"));
+  any_arr.push(&JsValue::from_str("resume"));
+  let start_or_resume = any(&any_arr).unwrap();
+
+  let idle_start = Array::new();
+  idle_start.push(&JsValue::from_str("idle"));
+  idle_start.push(&JsValue::from_str(&start_or_resume));
+  let idle_start = tuple(&idle_start).unwrap();
+
+  let running_stop = Array::new();
+  running_stop.push(&JsValue::from_str("run*"));
+  running_stop.push(&JsValue::from_str(&regex("^st(op|all)$", None).unwrap()));
+  let running_stop = tuple(&running_stop).unwrap();
+
+  let any_error = Array::new();
+  any_error.push(&JsValue::from_str("_"));
+  any_error.push(&JsValue::from_str("error"));
+  let any_error = tuple(&any_error).unwrap();
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&idle_start),
+    &Function::new_no_args("return 'running';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&running_stop),
+    &Function::new_with_args("values", "return values[0] + ' stopped';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&any_error),
+    &Function::new_no_args("return 'failed';"),
+  )
+  .unwrap();
+
+  let values = |state: &str, event: &str| {
+    let arr = Array::new();
+    arr.push(&JsValue::from_str(state));
+    arr.push(&JsValue::from_str(event));
+    arr
+  };
+
+  let result = match_tuple(&values("idle", "resume"), &patterns, None).unwrap();
+  assert_eq!(result.as_string().unwrap(), "running");
+  let result = match_tuple(&values("running", "stall"), &patterns, None).unwrap();
+  assert_eq!(result.as_string().unwrap(), "running stopped");
+  let result = match_tuple(&values("idle", "error"), &patterns, None).unwrap();
+  assert_eq!(result.as_string().unwrap(), "failed");
+  assert!(match_tuple(&values("idle", "stop"), &patterns, None).is_err());
+
+  let options = Object::new();
+  Reflect::set(
+    &options,
+    &JsValue::from_str("caseSensitive"),
+    &JsValue::from_bool(false),
+  )
+  .unwrap();
+  let result = match_tuple(&values("IDLE", "Start"), &patterns, Some(options)).unwrap();
+  assert_eq!(result.as_string().unwrap(), "running");
+
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'ignored';"),
+  )
+  .unwrap();
+  let result = match_tuple(&values("idle", "stop"), &patterns, None).unwrap();
+  assert_eq!(result.as_string().unwrap(), "ignored");
+
+  // Regex positions see numbers as strings, as top-level regex arms do.
+  let status = Array::new();
+  status.push(&JsValue::from_str(&regex("^4", None).unwrap()));
+  status.push(&JsValue::from_str("GET"));
+  let client_error = Object::new();
+  Reflect::set(
+    &client_error,
+    &JsValue::from_str(&tuple(&status).unwrap()),
+    &Function::new_no_args("return 'client error';"),
+  )
+  .unwrap();
+  let request = Array::of2(&JsValue::from(404), &JsValue::from_str("GET"));
+  let result = match_tuple(&request, &client_error, None).unwrap();
+  assert_eq!(result.as_string().unwrap(), "client error");
+  assert!(matches(
+    &JsValue::from(404),
+    &JsValue::from_str(&regex("^4", None).unwrap()),
+    None
+  )
+  .unwrap());
+
+  let too_short = Array::new();
+  too_short.push(&JsValue::from_str("idle"));
+  assert!(match_tuple(&too_short, &patterns, None).is_err());
+
+  let not_a_tuple = Object::new();
+  Reflect::set(
+    &not_a_tuple,
+    &JsValue::from_str("idle"),
+    &Function::new_no_args("return 'x';"),
+  )
+  .unwrap();
+  assert!(match_tuple(&values("idle", "start"), &not_a_tuple, None).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_create_machine() {
+  let log = Array::new();
+  let states = Object::new();
+  let idle = Object::new();
+  Reflect::set(
+    &idle,
+    &JsValue::from_str("exit"),
+    &Function::new_with_args("t", "this.push('exit ' + t.from + ' on ' + t.event);").bind(&log),
+  )
+  .unwrap();
+  let running = Object::new();
+  Reflect::set(
+    &running,
+    &JsValue::from_str("entry"),
+    &Function::new_with_args("t", "this.push('enter ' + t.to);").bind(&log),
+  )
+  .unwrap();
+  Reflect::set(&states, &JsValue::from_str("idle"), &idle).unwrap();
+  Reflect::set(&states, &JsValue::from_str("running"), &running).unwrap();
+  Reflect::set(&states, &JsValue::from_str("failed"), &Object::new()).unwrap();
+
+  let start = Array::new();
+  start.push(&JsValue::from_str("idle"));
+  start.push(&JsValue::from_str("start"));
+  let stop = Array::new();
+  stop.push(&JsValue::from_str("running"));
+  stop.push(&JsValue::from_str("stop"));
+  let has_budget = Function::new_with_args("event, ctx", "return ctx.budget > 0;");
+  let guarded = Array::new();
+  guarded.push(&JsValue::from_str("running"));
+  guarded.push(&JsValue::from_str(&when(&has_budget).unwrap()));
+  let error = Array::new();
+  error.push(&JsValue::from_str("_"));
+  error.push(&JsValue::from_str("error"));
+
+  let transitions = Object::new();
+  Reflect::set(
+    &transitions,
+    &JsValue::from_str(&tuple(&start).unwrap()),
+    &JsValue::from_str("running"),
+  )
+  .unwrap();
+  Reflect::set(
+    &transitions,
+    &JsValue::from_str(&tuple(&stop).unwrap()),
+    &Function::new_no_args("return 'idle';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &transitions,
+    &JsValue::from_str(&tuple(&error).unwrap()),
+    &JsValue::from_str("failed"),
+  )
+  .unwrap();
+
+  let context = Object::new();
+  Reflect::set(&context, &JsValue::from_str("budget"), &JsValue::from(0)).unwrap();
+  let config = Object::new();
+  Reflect::set(&config, &JsValue::from_str("states"), &states).unwrap();
+  Reflect::set(&config, &JsValue::from_str("transitions"), &transitions).unwrap();
+  Reflect::set(&config, &JsValue::from_str("context"), &context).unwrap();
+
+  let mut machine = create_machine(&config).unwrap();
+  assert_eq!(machine.state(), "idle");
+  assert!(machine.can(&JsValue::from_str("start")));
+  assert!(!machine.can(&JsValue::from_str("stop")));
+
+  assert_eq!(
+    machine.send(&JsValue::from_str("start")).unwrap(),
+    "running"
+  );
+  assert_eq!(log.length(), 2);
+  assert_eq!(log.get(0).as_string().unwrap(), "exit idle on start");
+  assert_eq!(log.get(1).as_string().unwrap(), "enter running");
+
+  let err = machine.send(&JsValue::from_str("start")).unwrap_err();
+  let name = Reflect::get(&err, &JsValue::from_str("name")).unwrap();
+  let code = Reflect::get(&err, &JsValue::from_str("code")).unwrap();
+  let state = Reflect::get(&err, &JsValue::from_str("state")).unwrap();
+  assert_eq!(name.as_string().unwrap(), "MachineError");
+  assert_eq!(code.as_string().unwrap(), "invalid-transition");
+  assert_eq!(state.as_string().unwrap(), "running");
+  assert_eq!(machine.state(), "running");
+
+  assert_eq!(machine.send(&JsValue::from_str("stop")).unwrap(), "idle");
+  assert_eq!(machine.send(&JsValue::from_str("error")).unwrap(), "failed");
+
+  Reflect::set(
+    &transitions,
+    &JsValue::from_str(&tuple(&guarded).unwrap()),
+    &JsValue::from_str("idle"),
+  )
+  .unwrap();
+  let mut machine = create_machine(&config).unwrap();
+  machine.send(&JsValue::from_str("start")).unwrap();
+  assert!(machine.send(&JsValue::from_str("retry")).is_err());
+  Reflect::set(&context, &JsValue::from_str("budget"), &JsValue::from(1)).unwrap();
+  assert_eq!(machine.send(&JsValue::from_str("retry")).unwrap(), "idle");
+
+  Reflect::set(
+    &transitions,
+    &JsValue::from_str(&tuple(&guarded).unwrap()),
+    &JsValue::from_str("nowhere"),
+  )
+  .unwrap();
+  let err = create_machine(&config).err().unwrap();
+  let code = Reflect::get(&err, &JsValue::from_str("code")).unwrap();
+  assert_eq!(code.as_string().unwrap(), "unknown-state");
+}
+
+#[wasm_bindgen_test]
+fn test_router() {
+  let router = Router::new(None).unwrap();
+  let log = Array::new();
+  let record = |label: &str| {
+    Function::new_with_args(
+      "topic, ctx",
+      &format!("this.push('{}:' + topic); return '{}';", label, label),
+    )
+    .bind(&log)
+  };
+
+  let broad = router.on("*.*", record("broad")).unwrap();
+  let exact = router.on("user.created", record("exact")).unwrap();
+  let audit = router.on("user.created", record("audit")).unwrap();
+  let user = router.on("user.*", record("user")).unwrap();
+  router.on("_", record("fallback")).unwrap();
+
+  let results = router
+    .dispatch(&JsValue::from_str("user.created"), JsValue::UNDEFINED)
+    .unwrap();
+  assert_eq!(results.length(), 2);
+  assert_eq!(results.get(0).as_string().unwrap(), "exact");
+  assert_eq!(results.get(1).as_string().unwrap(), "audit");
+
+  let results = router
+    .dispatch(&JsValue::from_str("user.login"), JsValue::UNDEFINED)
+    .unwrap();
+  assert_eq!(results.get(0).as_string().unwrap(), "user");
+
+  assert!(router.off(user).unwrap());
+  assert!(!router.off(user).unwrap());
+  let results = router
+    .dispatch(&JsValue::from_str("user.login"), JsValue::UNDEFINED)
+    .unwrap();
+  assert_eq!(results.get(0).as_string().unwrap(), "broad");
+  assert_eq!(
+    log.get(log.length() - 1).as_string().unwrap(),
+    "broad:user.login"
+  );
+
+  assert!(router.off(exact).unwrap());
+  let results = router
+    .dispatch(&JsValue::from_str("user.created"), JsValue::UNDEFINED)
+    .unwrap();
+  assert_eq!(results.length(), 1);
+  assert_eq!(results.get(0).as_string().unwrap(), "audit");
+
+  router.off(audit).unwrap();
+  router.off(broad).unwrap();
+  let results = router
+    .dispatch(&JsValue::from_str("user.created"), JsValue::UNDEFINED)
+    .unwrap();
+  assert_eq!(results.get(0).as_string().unwrap(), "fallback");
+
+  let with_context = Router::new(None).unwrap();
+  let is_admin = Function::new_with_args("topic, ctx", "return ctx.admin === true;");
+  with_context
+    .on(
+      &when(&is_admin).unwrap(),
+      Function::new_with_args("topic, ctx", "return ctx.name;"),
+    )
+    .unwrap();
+  let context = Object::new();
+  Reflect::set(&context, &JsValue::from_str("admin"), &JsValue::TRUE).unwrap();
+  Reflect::set(
+    &context,
+    &JsValue::from_str("name"),
+    &JsValue::from_str("root"),
+  )
+  .unwrap();
+  let results = with_context
+    .dispatch(&JsValue::from_str("anything"), context.into())
+    .unwrap();
+  assert_eq!(results.get(0).as_string().unwrap(), "root");
+  let results = with_context
+    .dispatch(&JsValue::from_str("anything"), JsValue::UNDEFINED)
+    .unwrap();
+  assert_eq!(results.length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_type_patterns() {
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&type_pattern("string").unwrap()),
+    &Function::new_no_args("return 'string';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&integer()),
+    &Function::new_no_args("return 'integer';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&finite()),
+    &Function::new_no_args("return 'finite';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&nan()),
+    &Function::new_no_args("return 'nan';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&type_pattern("boolean").unwrap()),
+    &Function::new_no_args("return 'boolean';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("a*"),
+    &Function::new_no_args("return 'wildcard';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'other';"),
+  )
+  .unwrap();
+
+  let check = |value: JsValue, expected: &str| {
+    let result = match_pattern(&value, &patterns, None).unwrap();
+    assert_eq!(result.as_string().unwrap(), expected);
+  };
+  check(JsValue::from_str("hello"), "string");
+  check(JsValue::from_str("apple"), "wildcard");
+  check(JsValue::from(42), "integer");
+  check(JsValue::from(4.2), "finite");
+  check(JsValue::from_f64(f64::NAN), "nan");
+  check(JsValue::from_f64(f64::INFINITY), "other");
+  check(JsValue::from_bool(false), "boolean");
+  check(JsValue::NULL, "other");
+
+  assert!(type_pattern("object").is_err());
+  assert!(matches(
+    &JsValue::UNDEFINED,
+    &JsValue::from_str(&type_pattern("undefined").unwrap()),
+    None
+  )
+  .unwrap());
+
+  let kind = which(&JsValue::from(1), &patterns, None).unwrap();
+  let kind = Reflect::get(&kind, &JsValue::from_str("kind")).unwrap();
+  assert_eq!(kind.as_string().unwrap(), "type");
+}
+
+#[wasm_bindgen_test]
+fn test_string_predicates() {
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&starts_with("img_")),
+    &Function::new_no_args("return 'image';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&ends_with(".tmp")),
+    &Function::new_no_args("return 'temporary';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&includes("a|b")),
+    &Function::new_no_args("return 'pipe';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&max_length(2.0).unwrap()),
+    &Function::new_no_args("return 'short';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'other';"),
+  )
+  .unwrap();
+
+  let check = |value: &str, options: Option<Object>, expected: &str| {
+    let result = match_pattern(&JsValue::from_str(value), &patterns, options).unwrap();
+    assert_eq!(result.as_string().unwrap(), expected);
+  };
+  check("img_cat.png", None, "image");
+  check("IMG_cat.png", None, "other");
+  check("cache.tmp", None, "temporary");
+  check("xa|by", None, "pipe");
+  check("ab", None, "short");
+  check("abc", None, "other");
+  let options = Object::new();
+  Reflect::set(
+    &options,
+    &JsValue::from_str("caseSensitive"),
+    &JsValue::from_bool(false),
+  )
+  .unwrap();
+  check("IMG_cat.png", Some(options), "image");
+  assert!(match_pattern(&JsValue::from(12), &patterns, None)
+    .unwrap()
+    .as_string()
+    .unwrap()
+    .eq("other"));
+
+  let length_three = length(3.0).unwrap();
+  assert!(matches(
+    &JsValue::from_str("abc"),
+    &JsValue::from_str(&length_three),
+    None
+  )
+  .unwrap());
+  assert!(matches(
+    &JsValue::from_str("abcd"),
+    &JsValue::from_str(&min_length(4.0).unwrap()),
+    None
+  )
+  .unwrap());
+  assert!(min_length(-1.0).is_err());
+  assert!(length(1.5).is_err());
+
+  let members = Array::new();
+  members.push(&JsValue::from_str(&starts_with("tmp")));
+  members.push(&JsValue::from_str("scratch"));
+  let excluded = not(&members).unwrap();
+  assert!(!matches(
+    &JsValue::from_str("tmp-1"),
+    &JsValue::from_str(&excluded),
+    None
+  )
+  .unwrap());
+  assert!(!matches(
+    &JsValue::from_str("scratch"),
+    &JsValue::from_str(&excluded),
+    None
+  )
+  .unwrap());
+  assert!(matches(
+    &JsValue::from_str("notes"),
+    &JsValue::from_str(&excluded),
+    None
+  )
+  .unwrap());
+  let either = any(&members).unwrap();
+  assert!(matches(
+    &JsValue::from_str("tmp-2"),
+    &JsValue::from_str(&either),
+    None
+  )
+  .unwrap());
+
+  let validated = Object::new();
+  Reflect::set(
+    &validated,
+    &JsValue::from_str(&either),
+    &Function::new_no_args("return 1;"),
+  )
+  .unwrap();
+  Reflect::set(
+    &validated,
+    &JsValue::from_str(&excluded),
+    &Function::new_no_args("return 2;"),
+  )
+  .unwrap();
+  assert_eq!(validate_patterns(&validated, None).unwrap().length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_strict_literals() {
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&lit(&JsValue::from(1)).unwrap()),
+    &Function::new_no_args("return 'number one';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("1"),
+    &Function::new_no_args("return 'string one';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("true"),
+    &Function::new_no_args("return 'string true';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("null"),
+    &Function::new_no_args("return 'string null';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'other';"),
+  )
+  .unwrap();
+
+  let strict = || {
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("strict"), &JsValue::TRUE).unwrap();
+    Some(options)
+  };
+  let check = |value: JsValue, options: Option<Object>, expected: &str| {
+    let result = match_pattern(&value, &patterns, options).unwrap();
+    assert_eq!(result.as_string().unwrap(), expected);
+  };
+
+  check(JsValue::from(1), None, "number one");
+  check(JsValue::from_str("1"), None, "string one");
+  check(JsValue::from_bool(true), None, "string true");
+  check(JsValue::NULL, None, "string null");
+
+  check(JsValue::from(1), strict(), "number one");
+  check(JsValue::from_str("1"), strict(), "string one");
+  check(JsValue::from_bool(true), strict(), "other");
+  check(JsValue::NULL, strict(), "other");
+  check(JsValue::from_str("null"), strict(), "string null");
+
+  let lit_true = lit(&JsValue::from_bool(true)).unwrap();
+  assert!(matches(
+    &JsValue::from_bool(true),
+    &JsValue::from_str(&lit_true),
+    strict()
+  )
+  .unwrap());
+  assert!(!matches(
+    &JsValue::from_str("true"),
+    &JsValue::from_str(&lit_true),
+    None
+  )
+  .unwrap());
+  assert!(!matches(
+    &JsValue::from_bool(true),
+    &JsValue::from_str("true"),
+    strict()
+  )
+  .unwrap());
+  assert!(lit(&Object::new()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_coerce_option() {
+  let numbers = Array::new();
+  numbers.push(&JsValue::from(42));
+  numbers.push(&JsValue::from(7));
+  let any_number = any(&numbers).unwrap();
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&any_number),
+    &Function::new_no_args("return 'number';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&lit(&JsValue::TRUE).unwrap()),
+    &Function::new_no_args("return 'yes';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("draft"),
+    &Function::new_no_args("return 'draft';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &Function::new_no_args("return 'other';"),
+  )
+  .unwrap();
+
+  let coerce = || {
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("coerce"), &JsValue::TRUE).unwrap();
+    Some(options)
+  };
+  let check = |value: &str, options: Option<Object>, expected: &str| {
+    let result = match_pattern(&JsValue::from_str(value), &patterns, options).unwrap();
+    assert_eq!(result.as_string().unwrap(), expected);
+  };
+
+  check("42", None, "other");
+  check("42", coerce(), "number");
+  check(" 7.0 ", coerce(), "number");
+  check("4e1", coerce(), "other");
+  check("true", None, "other");
+  check(" true", coerce(), "yes");
+  check("  draft ", coerce(), "draft");
+  check("Infinity", coerce(), "other");
+
+  let strings = Array::new();
+  strings.push(&JsValue::from_str("42"));
+  let not_42 = not(&strings).unwrap();
+  assert!(matches(&JsValue::from(42), &JsValue::from_str(&not_42), None).unwrap());
+  assert!(!matches(&JsValue::from(42), &JsValue::from_str(&not_42), coerce()).unwrap());
+
+  // Keys are coerced like values, so they still match their own spelling.
+  let spelled = Object::new();
+  for key in ["042", "1.0", "1e3"] {
+    Reflect::set(
+      &spelled,
+      &JsValue::from_str(key),
+      &Function::new_no_args(&format!("return '{}';", key)),
+    )
+    .unwrap();
+  }
+  let spelled_as = |value: &JsValue| {
+    match_pattern(value, &spelled, coerce())
+      .unwrap()
+      .as_string()
+      .unwrap()
+  };
+  assert_eq!(spelled_as(&JsValue::from_str("042")), "042");
+  assert_eq!(spelled_as(&JsValue::from(42)), "042");
+  assert_eq!(spelled_as(&JsValue::from_str("1")), "1.0");
+  assert_eq!(spelled_as(&JsValue::from_str("1.0")), "1.0");
+  assert_eq!(spelled_as(&JsValue::from(1000)), "1e3");
+
+  Reflect::set(
+    &spelled,
+    &JsValue::from_str(&lit(&JsValue::from_str("42")).unwrap()),
+    &Function::new_no_args("return 'lit';"),
+  )
+  .unwrap();
+  assert_eq!(spelled_as(&JsValue::from_str("42")), "lit");
+  assert_eq!(spelled_as(&JsValue::from(42)), "lit");
+  assert!(matches(
+    &JsValue::from_str("1e3"),
+    &JsValue::from_str("1e3"),
+    coerce()
+  )
+  .unwrap());
+
+  let narrowed = Object::new();
+  let f = Function::new_no_args("return 1;");
+  let number = type_pattern("number").unwrap();
+  Reflect::set(&narrowed, &JsValue::from_str(&not_42), &f).unwrap();
+  Reflect::set(&narrowed, &JsValue::from_str(&number), &f).unwrap();
+  let codes = diagnostic_codes(&validate_patterns(&narrowed, None).unwrap());
+  assert!(codes.contains(&("unreachable".to_string(), number.clone())));
+  let codes = diagnostic_codes(&validate_patterns(&narrowed, coerce()).unwrap());
+  assert!(!codes.iter().any(|(code, _)| code == "unreachable"));
+}
+
+#[wasm_bindgen_test]
+fn test_number_semantics() {
+  let patterns = Object::new();
+  let set = |key: &str, result: &str| {
+    Reflect::set(
+      &patterns,
+      &JsValue::from_str(key),
+      &Function::new_no_args(&format!("return '{}';", result)),
+    )
+    .unwrap();
+  };
+  set(&lit(&JsValue::from_f64(-0.0)).unwrap(), "negative zero");
+  set(&lit(&JsValue::from_f64(f64::NAN)).unwrap(), "nan");
+  set("Infinity", "infinity");
+  set(
+    &any(&Array::of1(&JsValue::from_f64(f64::NEG_INFINITY))).unwrap(),
+    "negative infinity",
+  );
+  set("_", "other");
+
+  let equality = |mode: &str| {
+    let options = Object::new();
+    Reflect::set(
+      &options,
+      &JsValue::from_str("equality"),
+      &JsValue::from_str(mode),
+    )
+    .unwrap();
+    Some(options)
+  };
+  let check = |value: f64, options: Option<Object>, expected: &str| {
+    let result = match_pattern(&JsValue::from_f64(value), &patterns, options).unwrap();
+    assert_eq!(result.as_string().unwrap(), expected);
+  };
+
+  check(f64::NAN, None, "nan");
+  check(0.0, None, "negative zero");
+  check(-0.0, None, "negative zero");
+  check(f64::INFINITY, None, "infinity");
+  check(f64::NEG_INFINITY, None, "negative infinity");
+  check(0.0, equality("sameValue"), "other");
+  check(-0.0, equality("sameValue"), "negative zero");
+  check(f64::NAN, equality("sameValue"), "nan");
+  check(0.0, equality("sameValueZero"), "negative zero");
+  for mode in ["samevalue", "strict"] {
+    assert!(match_pattern(&JsValue::from_f64(0.0), &patterns, equality(mode)).is_err());
+  }
+
+  let excluded = not(&Array::of1(&JsValue::from_f64(f64::NAN))).unwrap();
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&excluded),
+    &JsValue::from_str("a number"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("_"),
+    &JsValue::from_str("nan"),
+  )
+  .unwrap();
+  let options = Object::new();
+  Reflect::set(&options, &JsValue::from_str("constants"), &JsValue::TRUE).unwrap();
+  let result = match_pattern(&JsValue::from_f64(f64::NAN), &patterns, Some(options)).unwrap();
+  assert_eq!(result.as_string().unwrap(), "nan");
+}
+
+#[wasm_bindgen_test]
+fn test_bigint_values() {
+  let big = |n: i64| JsValue::from(js_sys::BigInt::from(n));
+  let patterns = Object::new();
+  let set = |key: &str, result: &str| {
+    Reflect::set(
+      &patterns,
+      &JsValue::from_str(key),
+      &JsValue::from_str(result),
+    )
+    .unwrap();
+  };
+  set(&lit(&big(10)).unwrap(), "ten");
+  set(
+    &any(&Array::of2(&big(20), &big(30))).unwrap(),
+    "twenty or thirty",
+  );
+  set(&range(&big(-5), &JsValue::from_f64(5.5)).unwrap(), "small");
+  set(&type_pattern("bigint").unwrap(), "bigint");
+  set("_", "other");
+
+  let check = |value: JsValue, expected: &str| {
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("constants"), &JsValue::TRUE).unwrap();
+    let result = match_pattern(&value, &patterns, Some(options)).unwrap();
+    assert_eq!(result.as_string().unwrap(), expected);
+  };
+
+  check(big(10), "ten");
+  check(JsValue::from(10), "other");
+  check(big(30), "twenty or thirty");
+  check(big(5), "small");
+  check(JsValue::from_f64(-5.0), "small");
+  check(JsValue::from_f64(5.75), "other");
+  check(big(6), "bigint");
+
+  assert!(range(&big(5), &JsValue::from(1)).is_err());
+  assert!(range(&JsValue::from_str("a"), &big(1)).is_err());
+  assert!(range(&JsValue::from_f64(f64::NAN), &big(1)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_date_patterns() {
+  // 2024-03-15T09:30:00Z, a Friday.
+  let friday = 1_710_495_000_000.0;
+  let date = |ms: f64| JsValue::from(js_sys::Date::new(&JsValue::from_f64(ms)));
+  let hour_ms = 3_600_000.0;
+
+  let check =
+    |pattern: &str, value: &JsValue| matches(value, &JsValue::from_str(pattern), None).unwrap();
+
+  assert!(check(&before(&date(friday + 1.0)).unwrap(), &date(friday)));
+  assert!(!check(&before(&date(friday)).unwrap(), &date(friday)));
+  assert!(check(
+    &after(&JsValue::from_f64(friday - 1.0)).unwrap(),
+    &date(friday)
+  ));
+  let window = between(&date(friday), &date(friday + hour_ms)).unwrap();
+  assert!(check(&window, &date(friday)));
+  assert!(!check(&window, &date(friday + hour_ms)));
+  assert!(!check(&window, &JsValue::from_f64(friday)));
+
+  assert!(check(
+    &weekday(&JsValue::from(5), None).unwrap(),
+    &date(friday)
+  ));
+  let weekend = weekday(&Array::of2(&JsValue::from(0), &JsValue::from(6)), None).unwrap();
+  assert!(!check(&weekend, &date(friday)));
+  // 15:00 at +14:00 is already Saturday.
+  let weekend_east = weekday(
+    &Array::of2(&JsValue::from(0), &JsValue::from(6)),
+    Some("+14:00".to_string()),
+  )
+  .unwrap();
+  assert!(check(&weekend_east, &date(friday + 5.0 * hour_ms)));
+
+  assert!(check(&hour(9.0, None, None).unwrap(), &date(friday)));
+  assert!(check(&hour(9.0, Some(17.0), None).unwrap(), &date(friday)));
+  assert!(!check(
+    &hour(9.0, Some(17.0), Some("-05:00".to_string())).unwrap(),
+    &date(friday)
+  ));
+  assert!(check(
+    &hour(22.0, Some(6.0), Some("-05:00".to_string())).unwrap(),
+    &date(friday)
+  ));
+
+  assert!(hour(24.0, None, None).is_err());
+  assert!(hour(9.0, Some(9.0), None).is_err());
+  assert!(weekday(&JsValue::from(7), None).is_err());
+  assert!(weekday(&JsValue::from(1), Some("Europe/Paris".to_string())).is_err());
+  assert!(between(&date(friday), &date(friday - 1.0)).is_err());
+  assert!(before(&JsValue::from_str("2024-03-15")).is_err());
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&lit(&date(friday)).unwrap()),
+    &JsValue::from_str("exact instant"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&type_pattern("date").unwrap()),
+    &JsValue::from_str("some date"),
+  )
+  .unwrap();
+  let options = || {
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("constants"), &JsValue::TRUE).unwrap();
+    Some(options)
+  };
+  let result = match_pattern(&date(friday), &patterns, options()).unwrap();
+  assert_eq!(result.as_string().unwrap(), "exact instant");
+  let result = match_pattern(&date(friday + 1.0), &patterns, options()).unwrap();
+  assert_eq!(result.as_string().unwrap(), "some date");
+}
+
+#[wasm_bindgen_test]
+fn test_cron_patterns() {
+  // 2024-03-15T09:30:00Z, a Friday.
+  let friday = 1_710_495_000_000.0;
+  let hour_ms = 3_600_000.0;
+  let date = |ms: f64| JsValue::from(js_sys::Date::new(&JsValue::from_f64(ms)));
+  let check =
+    |pattern: &str, value: &JsValue| matches(value, &JsValue::from_str(pattern), None).unwrap();
+
+  let business_hours = cron("0-59 9-17 * * MON-FRI", None).unwrap();
+  assert!(check(&business_hours, &date(friday)));
+  assert!(check(&business_hours, &JsValue::from_f64(friday)));
+  assert!(!check(&business_hours, &date(friday + 9.0 * hour_ms)));
+  assert!(!check(&business_hours, &date(friday + 24.0 * hour_ms)));
+  assert!(!check(&business_hours, &JsValue::from_str("2024-03-15")));
+
+  assert!(check(&cron("*/15 * * * *", None).unwrap(), &date(friday)));
+  assert!(!check(&cron("*/20 * * * *", None).unwrap(), &date(friday)));
+  assert!(check(&cron("30 9 15 mar *", None).unwrap(), &date(friday)));
+  // Day-of-month and day-of-week match either way when both are restricted.
+  assert!(check(&cron("* * 1 * 5", None).unwrap(), &date(friday)));
+  assert!(check(
+    &cron("* * * * 0,7", None).unwrap(),
+    &date(friday + 48.0 * hour_ms)
+  ));
+  // `SUN` ends a range as 7, so a week can run Monday to Sunday.
+  let whole_week = cron("* * * * MON-SUN", None).unwrap();
+  assert!(check(&whole_week, &date(friday + 48.0 * hour_ms)));
+  assert!(check(&whole_week, &date(friday + 72.0 * hour_ms)));
+  assert!(!check(
+    &cron("* * * * SAT-SUN", None).unwrap(),
+    &date(friday)
+  ));
+  assert!(check(
+    &cron("30 18 * * FRI", Some("+08:45".to_string())).unwrap(),
+    &date(friday + 15.0 * 60_000.0)
+  ));
+
+  assert!(cron("* * * *", None).is_err());
+  assert!(cron("60 * * * *", None).is_err());
+  assert!(cron("* 17-9 * * *", None).is_err());
+  assert!(cron("*/0 * * * *", None).is_err());
+  assert!(cron("* * * * FUNDAY", None).is_err());
+  assert!(cron("* * * * *", Some("America/New_York".to_string())).is_err());
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&business_hours),
+    &Function::new_no_args("return 'open';"),
+  )
+  .unwrap();
+  let result = which(&date(friday), &patterns, None).unwrap();
+  assert_eq!(
+    Reflect::get(&result, &JsValue::from_str("kind"))
+      .unwrap()
+      .as_string()
+      .unwrap(),
+    "cron"
+  );
+}
+
+#[wasm_bindgen_test]
+fn test_unicode_case_folding() {
+  let options = |locale: Option<&str>| {
+    let options = Object::new();
+    Reflect::set(
+      &options,
+      &JsValue::from_str("caseSensitive"),
+      &JsValue::FALSE,
+    )
+    .unwrap();
+    if let Some(locale) = locale {
+      Reflect::set(
+        &options,
+        &JsValue::from_str("locale"),
+        &JsValue::from_str(locale),
+      )
+      .unwrap();
+    }
+    Some(options)
+  };
+  let check = |pattern: &str, value: &str, locale: Option<&str>| {
+    matches(
+      &JsValue::from_str(value),
+      &JsValue::from_str(pattern),
+      options(locale),
+    )
+    .unwrap()
+  };
+
+  assert!(check("straße", "STRASSE", None));
+  assert!(check("ÉCOLE", "école", None));
+  assert!(check(
+    &any(&Array::of1(&JsValue::from_str("straße"))).unwrap(),
+    "Strasse",
+    None
+  ));
+  assert!(!check(
+    &not(&Array::of1(&JsValue::from_str("ÉCOLE"))).unwrap(),
+    "école",
+    None
+  ));
+  assert!(check(
+    &lit(&JsValue::from_str("Straße")).unwrap(),
+    "STRASSE",
+    None
+  ));
+  assert!(check(&starts_with("STRASS"), "straße 1", None));
+  assert!(check("stra*e", "STRASSE", None));
+  assert!(check(&regex("^strasse$", None).unwrap(), "STRAßE", None));
+  assert!(check("straße", "STRASSE", Some("de")));
+
+  assert!(check("istanbul", "İSTANBUL", Some("tr")));
+  assert!(!check("istanbul", "ISTANBUL", Some("tr")));
+  assert!(check("ırmak", "IRMAK", Some("tr-TR")));
+  assert!(check("istanbul", "ISTANBUL", None));
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("help"),
+    &Function::new_no_args("return 'help';"),
+  )
+  .unwrap();
+  let result = match_pattern(&JsValue::from_str("HELP"), &patterns, options(None)).unwrap();
+  assert_eq!(result.as_string().unwrap(), "help");
+}
+
+#[wasm_bindgen_test]
+fn test_case_insensitive_exact_keys() {
+  let insensitive = || {
+    let options = Object::new();
+    Reflect::set(
+      &options,
+      &JsValue::from_str("caseSensitive"),
+      &JsValue::FALSE,
+    )
+    .unwrap();
+    options
+  };
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("help"),
+    &Function::new_no_args("return 'help';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&lit(&JsValue::from_str("Quit")).unwrap()),
+    &Function::new_no_args("return 'quit';"),
+  )
+  .unwrap();
+
+  let result = match_pattern(&JsValue::from_str("HELP"), &patterns, Some(insensitive())).unwrap();
+  assert_eq!(result.as_string().unwrap(), "help");
+  let result = match_pattern(&JsValue::from_str("qUIT"), &patterns, Some(insensitive())).unwrap();
+  assert_eq!(result.as_string().unwrap(), "quit");
+  assert!(match_pattern(&JsValue::from_str("HELP"), &patterns, None).is_err());
+
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("Help"),
+    &Function::new_no_args("return 'Help';"),
+  )
+  .unwrap();
+  assert!(match_pattern(&JsValue::from_str("help"), &patterns, Some(insensitive())).is_err());
+  assert!(which(&JsValue::from_str("help"), &patterns, Some(insensitive())).is_err());
+  let result = match_pattern(&JsValue::from_str("Help"), &patterns, None).unwrap();
+  assert_eq!(result.as_string().unwrap(), "Help");
+
+  let diagnostics = validate_patterns(&patterns, Some(insensitive())).unwrap();
+  let collision = diagnostics
+    .iter()
+    .find(|d| {
+      Reflect::get(d, &JsValue::from_str("code"))
+        .unwrap()
+        .as_string()
+        == Some("case-collision".into())
+    })
+    .expect("expected a case-collision diagnostic");
+  assert_eq!(
+    Reflect::get(&collision, &JsValue::from_str("key"))
+      .unwrap()
+      .as_string()
+      .unwrap(),
+    "Help"
+  );
+
+  let router = Router::new(Some(insensitive())).unwrap();
+  router
+    .on("ping", Function::new_no_args("return 1;"))
+    .unwrap();
+  router
+    .on("ping", Function::new_no_args("return 2;"))
+    .unwrap();
+  assert!(router
+    .on("PING", Function::new_no_args("return 3;"))
+    .is_err());
+  assert_eq!(
+    router
+      .dispatch(&JsValue::from_str("Ping"), JsValue::UNDEFINED)
+      .unwrap()
+      .length(),
+    2
+  );
+}
+
+#[wasm_bindgen_test]
+fn test_normalize_option() {
+  let normalizing = |case_sensitive: bool| {
+    let normalize = Object::new();
+    for flag in ["trim", "collapseWhitespace", "stripDiacritics"] {
+      Reflect::set(&normalize, &JsValue::from_str(flag), &JsValue::TRUE).unwrap();
+    }
+    Reflect::set(
+      &normalize,
+      &JsValue::from_str("form"),
+      &JsValue::from_str("NFC"),
+    )
+    .unwrap();
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("normalize"), &normalize).unwrap();
+    Reflect::set(
+      &options,
+      &JsValue::from_str("caseSensitive"),
+      &JsValue::from_bool(case_sensitive),
+    )
+    .unwrap();
+    options
+  };
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("new  york"),
+    &Function::new_no_args("return 'exact';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&any(&Array::of2(&"Zürich".into(), &"Genève".into())).unwrap()),
+    &Function::new_no_args("return 'any';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&starts_with("São")),
+    &Function::new_no_args("return 'prefix';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("Mün*"),
+    &Function::new_no_args("return 'wildcard';"),
+  )
+  .unwrap();
+
+  let matched = |value: &str, options: Option<Object>| {
+    match_pattern(&JsValue::from_str(value), &patterns, options)
+      .ok()
+      .and_then(|result| result.as_string())
+  };
+  assert_eq!(
+    matched("  New\tYork ", Some(normalizing(false))).as_deref(),
+    Some("exact")
+  );
+  assert_eq!(
+    matched(" new york", Some(normalizing(true))).as_deref(),
+    Some("exact")
+  );
+  assert_eq!(
+    matched("zurich", Some(normalizing(false))).as_deref(),
+    Some("any")
+  );
+  assert_eq!(
+    matched("Geneve", Some(normalizing(true))).as_deref(),
+    Some("any")
+  );
+  assert_eq!(
+    matched(" Sao Paulo", Some(normalizing(true))).as_deref(),
+    Some("prefix")
+  );
+  assert_eq!(
+    matched("Munchen", Some(normalizing(true))).as_deref(),
+    Some("wildcard")
+  );
+  assert_eq!(matched("Munchen", None), None);
+  assert_eq!(matched(" new york", None), None);
+
+  let literal = Object::new();
+  Reflect::set(
+    &literal,
+    &JsValue::from_str(&lit(&JsValue::from_str("Foo")).unwrap()),
+    &Function::new_no_args("return 'lit';"),
+  )
+  .unwrap();
+  for (value, case_sensitive) in [("  Foo", true), ("foo  ", false)] {
+    assert_eq!(
+      match_pattern(
+        &JsValue::from_str(value),
+        &literal,
+        Some(normalizing(case_sensitive))
+      )
+      .unwrap()
+      .as_string()
+      .as_deref(),
+      Some("lit")
+    );
+  }
+  assert!(match_pattern(&JsValue::from_str("  Foo"), &literal, None).is_err());
+
+  // Precomposed and decomposed forms compare equal once normalized.
+  let options = Object::new();
+  let normalize = Object::new();
+  Reflect::set(
+    &normalize,
+    &JsValue::from_str("form"),
+    &JsValue::from_str("NFC"),
+  )
+  .unwrap();
+  Reflect::set(&options, &JsValue::from_str("normalize"), &normalize).unwrap();
+  assert!(matches(
+    &JsValue::from_str("Cafe\u{301}"),
+    &JsValue::from_str("Café"),
+    Some(options)
+  )
+  .unwrap());
+
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(" new york "),
+    &Function::new_no_args("return 'padded';"),
+  )
+  .unwrap();
+  assert!(match_pattern(
+    &JsValue::from_str("new york"),
+    &patterns,
+    Some(normalizing(true))
+  )
+  .is_err());
+  let diagnostics = validate_patterns(&patterns, Some(normalizing(true))).unwrap();
+  assert!(diagnostics.iter().any(|d| {
+    Reflect::get(&d, &JsValue::from_str("code"))
+      .unwrap()
+      .as_string()
+      == Some("case-collision".into())
+  }));
+  let with_normalize = |name: &str, value: &JsValue| {
+    let normalize = Object::new();
+    Reflect::set(&normalize, &JsValue::from_str(name), value).unwrap();
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from_str("normalize"), &normalize).unwrap();
+    Some(options)
+  };
+  for form in ["nfc", "NFX"] {
+    assert!(match_pattern(
+      &JsValue::from_str("new york"),
+      &patterns,
+      with_normalize("form", &JsValue::from_str(form))
+    )
+    .is_err());
+  }
+  assert!(match_pattern(
+    &JsValue::from_str("new york"),
+    &patterns,
+    with_normalize("form", &JsValue::from(1))
+  )
+  .is_err());
+
+  // Normalized spellings of a `not()` value get past it, so later arms stay reachable.
+  let narrowed = Object::new();
+  let f = Function::new_no_args("return 1;");
+  let space = starts_with(" ");
+  Reflect::set(
+    &narrowed,
+    &JsValue::from_str(&not(&Array::of1(&"café".into())).unwrap()),
+    &f,
+  )
+  .unwrap();
+  Reflect::set(&narrowed, &JsValue::from_str(&space), &f).unwrap();
+  let codes = diagnostic_codes(&validate_patterns(&narrowed, None).unwrap());
+  assert!(codes.contains(&("unreachable".to_string(), space.clone())));
+  let codes = diagnostic_codes(
+    &validate_patterns(
+      &narrowed,
+      with_normalize("collapseWhitespace", &JsValue::TRUE),
+    )
+    .unwrap(),
+  );
+  assert!(!codes.iter().any(|(code, _)| code == "unreachable"));
+}
+
+#[wasm_bindgen_test]
+fn test_fuzzy_patterns() {
+  let check = |pattern: &str, value: &str| {
+    matches(&JsValue::from_str(value), &JsValue::from_str(pattern), None).unwrap()
+  };
+
+  let deploy = fuzzy("deploy", Some(2.0)).unwrap();
+  assert!(check(&deploy, "deploy"));
+  assert!(check(&deploy, "deplyo"));
+  assert!(check(&deploy, "dploy"));
+  assert!(check(&deploy, "redeploy"));
+  assert!(!check(&deploy, "destroy"));
+  assert!(!check(&fuzzy("deploy", None).unwrap(), "Deplyo"));
+  assert!(!check(&fuzzy("deploy", None).unwrap(), "dpelyo"));
+  assert!(!matches(
+    &JsValue::from_f64(1.0),
+    &JsValue::from_str(&fuzzy("1", None).unwrap()),
+    None
+  )
+  .unwrap());
+
+  assert!(fuzzy("", None).is_err());
+  assert!(fuzzy("deploy", Some(1.5)).is_err());
+  assert!(fuzzy("deploy", Some(9.0)).is_err());
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("deploy"),
+    &Function::new_no_args("return 'exact';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&deploy),
+    &Function::new_with_args("value, context, distance", "return `${value}:${distance}`;"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("dep*"),
+    &Function::new_no_args("return 'wildcard';"),
+  )
+  .unwrap();
+
+  let result = |value: &str, options: Option<Object>| {
+    match_pattern(&JsValue::from_str(value), &patterns, options)
+      .unwrap()
+      .as_string()
+      .unwrap()
+  };
+  assert_eq!(result("deploy", None), "exact");
+  assert_eq!(result("depoly", None), "wildcard");
+  assert_eq!(result("edploy", None), "edploy:1");
+
+  let insensitive = Object::new();
+  Reflect::set(
+    &insensitive,
+    &JsValue::from_str("caseSensitive"),
+    &JsValue::FALSE,
+  )
+  .unwrap();
+  assert_eq!(result("EDPLOY!", Some(insensitive)), "EDPLOY!:2");
+
+  let matched = which(&JsValue::from_str("edploy"), &patterns, None).unwrap();
+  assert_eq!(
+    Reflect::get(&matched, &JsValue::from_str("kind"))
+      .unwrap()
+      .as_string()
+      .unwrap(),
+    "fuzzy"
+  );
+}
+
+#[wasm_bindgen_test]
+fn test_best_match() {
+  let patterns = Object::new();
+  for (key, result) in [
+    ("deploy".to_string(), "exact"),
+    (fuzzy("deploy", Some(2.0)).unwrap(), "fuzzy"),
+    ("d*".to_string(), "loose"),
+    ("depl*".to_string(), "specific"),
+    ("_".to_string(), "default"),
+  ] {
+    Reflect::set(
+      &patterns,
+      &JsValue::from_str(&key),
+      &Function::new_no_args(&format!("return '{}';", result)),
+    )
+    .unwrap();
+  }
+  let field = |best: &Object, name: &str| Reflect::get(best, &JsValue::from_str(name)).unwrap();
+
+  let best = best_match(&JsValue::from_str("deploy"), &patterns, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "exact");
+  assert_eq!(field(&best, "score").as_f64().unwrap(), 1.0);
+
+  // The wildcard arms win under `match`, but the fuzzy arm scores higher.
+  let best = best_match(&JsValue::from_str("deplyo"), &patterns, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "fuzzy");
+  assert_eq!(field(&best, "kind").as_string().unwrap(), "fuzzy");
+  assert_eq!(field(&best, "distance").as_f64().unwrap(), 1.0);
+  assert!((field(&best, "score").as_f64().unwrap() - 5.0 / 6.0).abs() < 1e-9);
+
+  let best = best_match(&JsValue::from_str("deplete"), &patterns, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "specific");
+  assert!(field(&best, "distance").is_undefined());
+
+  let best = best_match(&JsValue::from_str("build"), &patterns, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "default");
+  assert_eq!(field(&best, "kind").as_string().unwrap(), "default");
+  assert_eq!(field(&best, "score").as_f64().unwrap(), 0.0);
+
+  // `some()` and `type()` accept nearly anything, so any wildcard beats them.
+  let broad = Object::assign(&Object::new(), &patterns);
+  for (key, result) in [
+    (type_pattern("string").unwrap(), "string"),
+    (some(), "some"),
+  ] {
+    Reflect::set(
+      &broad,
+      &JsValue::from_str(&key),
+      &Function::new_no_args(&format!("return '{}';", result)),
+    )
+    .unwrap();
+  }
+  let best = best_match(&JsValue::from_str("deplete"), &broad, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "specific");
+  let best = best_match(&JsValue::from_str("dig"), &broad, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "loose");
+  let best = best_match(&JsValue::from_str("build"), &broad, None).unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "some");
+  assert!((field(&best, "score").as_f64().unwrap() - 0.1).abs() < 1e-9);
+
+  let options = Object::new();
+  Reflect::set(
+    &options,
+    &JsValue::from_str("score"),
+    &Function::new_with_args(
+      "value, candidate",
+      "return candidate.kind === 'wildcard' ? candidate.key.length : candidate.score;",
+    ),
+  )
+  .unwrap();
+  let best = best_match(
+    &JsValue::from_str("deplyo"),
+    &patterns,
+    Some(options.clone()),
+  )
+  .unwrap();
+  assert_eq!(field(&best, "result").as_string().unwrap(), "specific");
+  assert_eq!(field(&best, "score").as_f64().unwrap(), 5.0);
+
+  Reflect::set(
+    &options,
+    &JsValue::from_str("score"),
+    &Function::new_no_args("return 'high';"),
+  )
+  .unwrap();
+  assert!(best_match(&JsValue::from_str("deplyo"), &patterns, Some(options)).is_err());
+
+  Reflect::delete_property(&patterns, &JsValue::from_str("_")).unwrap();
+  assert!(best_match(&JsValue::from_str("build"), &patterns, None).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_phonetic_patterns() {
+  let check = |pattern: &str, value: &str| {
+    matches(&JsValue::from_str(value), &JsValue::from_str(pattern), None).unwrap()
+  };
+
+  let smith = sounds_like("Smith", None).unwrap();
+  assert!(check(&smith, "Smyth"));
+  assert!(check(&smith, "schmidt"));
+  assert!(!check(&smith, "Jones"));
+  assert!(!matches(&JsValue::from_f64(1.0), &JsValue::from_str(&smith), None).unwrap());
+
+  let catherine = sounds_like("Catherine", Some("doubleMetaphone".into())).unwrap();
+  assert!(check(&catherine, "Kathryn"));
+  assert!(check(&sounds_like("José", None).unwrap(), "jose"));
+
+  let robert = sounds_like("Robert", Some("soundex".into())).unwrap();
+  assert_eq!(robert, "sound::soundex::R163");
+  assert!(check(&robert, "Rupert"));
+  assert!(!check(&robert, "Rubin"));
+  assert!(check(
+    &sounds_like("Ashcraft", Some("soundex".into())).unwrap(),
+    "Ashcroft"
+  ));
+  assert!(check(
+    &sounds_like("Pfister", Some("soundex".into())).unwrap(),
+    "Pfyster"
+  ));
+
+  assert!(sounds_like("Smith", Some("metaphone".into())).is_err());
+  assert!(sounds_like("42", None).is_err());
+
+  let patterns = Object::new();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&smith),
+    &Function::new_no_args("return 'phonetic';"),
+  )
+  .unwrap();
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str(&fuzzy("Smith", None).unwrap()),
+    &Function::new_no_args("return 'fuzzy';"),
+  )
+  .unwrap();
+  let result = |value: &str| {
+    match_pattern(&JsValue::from_str(value), &patterns, None)
+      .unwrap()
+      .as_string()
+      .unwrap()
+  };
+  assert_eq!(result("Smyth"), "fuzzy");
+  assert_eq!(result("Schmidt"), "phonetic");
+
+  Reflect::set(
+    &patterns,
+    &JsValue::from_str("sound::soundex::"),
+    &Function::new_no_args("return 'broken';"),
+  )
+  .unwrap();
+  let diagnostics = validate_patterns(&patterns, None).unwrap();
+  assert!(diagnostics.iter().any(|d| {
+    Reflect::get(&d, &JsValue::from_str("code"))
+      .unwrap()
+      .as_string()
+      == Some("malformed-key".into())
+  }));
+}